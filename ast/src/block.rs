@@ -1,12 +1,12 @@
 use rimu_meta::Spanned;
 
-use crate::{expression::Expression, SpannedExpression};
+use crate::{expression::Expression, ListItem, ObjectEntry, SpannedExpression};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Block {
     Expression(Expression),
-    List(Vec<SpannedBlock>),
-    Object(Vec<(Spanned<String>, SpannedBlock)>),
+    List(Vec<ListItem<SpannedBlock>>),
+    Object(Vec<ObjectEntry<SpannedBlock>>),
     Function {
         args: Vec<Spanned<String>>,
         body: Box<SpannedBlock>,
@@ -0,0 +1,45 @@
+use rimu_meta::Spanned;
+use std::fmt;
+
+use crate::SpannedExpression;
+
+/// An item of a list, either in an [`Expression`](crate::Expression) or a
+/// [`Block`](crate::Block).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ListItem<T> {
+    /// A single item.
+    Item(T),
+    /// A list, whose items are spread into the list (`...x`).
+    Spread(T),
+}
+
+/// An entry of an object, either in an [`Expression`](crate::Expression) or a
+/// [`Block`](crate::Block).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ObjectEntry<T> {
+    /// A single key and value.
+    Entry(Spanned<String>, T),
+    /// A single value, with a key computed from an expression (`[x]: y`).
+    Computed(SpannedExpression, T),
+    /// An object, whose entries are spread into the object (`...x`).
+    Spread(T),
+}
+
+impl<T: fmt::Display> fmt::Display for ListItem<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListItem::Item(item) => write!(f, "{}", item),
+            ListItem::Spread(list) => write!(f, "...{}", list),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ObjectEntry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectEntry::Entry(key, value) => write!(f, "\"{}\": {}", key, value),
+            ObjectEntry::Computed(key, value) => write!(f, "[{}]: {}", key, value),
+            ObjectEntry::Spread(object) => write!(f, "...{}", object),
+        }
+    }
+}
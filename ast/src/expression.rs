@@ -2,7 +2,7 @@ use rimu_meta::Spanned;
 use rust_decimal::Decimal;
 use std::fmt;
 
-use crate::{BinaryOperator, UnaryOperator};
+use crate::{BinaryOperator, ListItem, ObjectEntry, UnaryOperator};
 
 /// An expression represents an entity which can be evaluated to a value.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -23,10 +23,23 @@ pub enum Expression {
     Identifier(String),
 
     /// Literal list.
-    List(Vec<SpannedExpression>),
+    List(Vec<ListItem<SpannedExpression>>),
 
     /// Literal key-value object.
-    Object(Vec<(Spanned<String>, SpannedExpression)>),
+    Object(Vec<ObjectEntry<SpannedExpression>>),
+
+    /// List comprehension (`[x * 2 for x in xs if x > 1]`).
+    ListComprehension {
+        item: Box<SpannedExpression>,
+        clauses: Vec<Spanned<ComprehensionClause>>,
+    },
+
+    /// Object comprehension (`{ (k): v for k, v in obj }`).
+    ObjectComprehension {
+        key: Box<SpannedExpression>,
+        value: Box<SpannedExpression>,
+        clauses: Vec<Spanned<ComprehensionClause>>,
+    },
 
     /// Function
     Function {
@@ -53,16 +66,21 @@ pub enum Expression {
         args: Vec<SpannedExpression>,
     },
 
-    /// Get index operation (`a[x]`).
+    /// Get index operation (`a[x]`), or optional get index operation
+    /// (`a?[x]`), which is null if the container is null or the index is
+    /// missing.
     GetIndex {
         container: Box<SpannedExpression>,
         index: Box<SpannedExpression>,
+        optional: bool,
     },
 
-    /// Get key operation (`c.z`).
+    /// Get key operation (`c.z`), or optional get key operation (`c?.z`),
+    /// which is null if the container is null or the key is missing.
     GetKey {
         container: Box<SpannedExpression>,
         key: Spanned<String>,
+        optional: bool,
     },
 
     /// Slice operation (`b[x:y]`).
@@ -77,6 +95,49 @@ pub enum Expression {
 
 pub type SpannedExpression = Spanned<Expression>;
 
+/// A clause of a list or object comprehension.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ComprehensionClause {
+    /// Evaluate the rest of the comprehension for each item of a list (`for
+    /// item in list`, or `for index, item in list`), or each entry of an object
+    /// (`for key in object`, or `for key, value in object`).
+    For {
+        variables: Vec<Spanned<String>>,
+        iterable: SpannedExpression,
+    },
+
+    /// Skip the rest of the comprehension unless the condition is truthy (`if
+    /// x > 1`).
+    If(SpannedExpression),
+}
+
+impl fmt::Display for ComprehensionClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComprehensionClause::For {
+                variables,
+                iterable,
+            } => {
+                let variables = variables
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "for {} in {}", variables, iterable)
+            }
+            ComprehensionClause::If(condition) => write!(f, "if {}", condition),
+        }
+    }
+}
+
+fn clauses_to_string(clauses: &[Spanned<ComprehensionClause>]) -> String {
+    clauses
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -103,11 +164,19 @@ impl fmt::Display for Expression {
             Expression::Object(object) => {
                 let entries = object
                     .iter()
-                    .map(|(key, value)| format!("\"{}\": {}", key, value))
+                    .map(ToString::to_string)
                     .collect::<Vec<String>>()
                     .join(", ");
                 write!(f, "{{{}}}", entries)
             }
+            Expression::ListComprehension { item, clauses } => {
+                write!(f, "[{} {}]", item, clauses_to_string(clauses))
+            }
+            Expression::ObjectComprehension {
+                key,
+                value,
+                clauses,
+            } => write!(f, "{{({}): {} {}}}", key, value, clauses_to_string(clauses)),
             Expression::Identifier(identifier) => write!(f, "{}", identifier),
             Expression::Unary { right, operator } => write!(f, "{}{}", operator, right),
             Expression::Binary {
@@ -123,8 +192,22 @@ impl fmt::Display for Expression {
                     .join(", ");
                 write!(f, "{}({})", function, args)
             }
-            Expression::GetIndex { container, index } => write!(f, "{}[{}]", container, index),
-            Expression::GetKey { container, key } => write!(f, "{}.{}", container, key),
+            Expression::GetIndex {
+                container,
+                index,
+                optional,
+            } => {
+                let optional = if *optional { "?" } else { "" };
+                write!(f, "{}{}[{}]", container, optional, index)
+            }
+            Expression::GetKey {
+                container,
+                key,
+                optional,
+            } => {
+                let optional = if *optional { "?" } else { "" };
+                write!(f, "{}{}.{}", container, optional, key)
+            }
             Expression::GetSlice {
                 container,
                 start,
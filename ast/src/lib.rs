@@ -1,7 +1,9 @@
 mod block;
+mod entry;
 mod expression;
 mod operator;
 
 pub use block::{Block, SpannedBlock};
-pub use expression::{Expression, SpannedExpression};
+pub use entry::{ListItem, ObjectEntry};
+pub use expression::{ComprehensionClause, Expression, SpannedExpression};
 pub use operator::{BinaryOperator, UnaryOperator};
@@ -33,6 +33,9 @@ pub enum BinaryOperator {
     LessEqual,
     Equal,
     NotEqual,
+    In,
+    NullCoalesce,
+    Pipe,
 }
 
 impl Display for BinaryOperator {
@@ -52,6 +55,9 @@ impl Display for BinaryOperator {
             BinaryOperator::Or => write!(f, "||"),
             BinaryOperator::Xor => write!(f, "^"),
             BinaryOperator::Rem => write!(f, "%"),
+            BinaryOperator::In => write!(f, "in"),
+            BinaryOperator::NullCoalesce => write!(f, "??"),
+            BinaryOperator::Pipe => write!(f, "|>"),
         }
     }
 }
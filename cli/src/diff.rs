@@ -0,0 +1,373 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    io::{Read, Write},
+    process::ExitCode,
+};
+
+use clap::Args;
+use clio::{Input, Output};
+use rimu::{Environment, SerdeValue};
+
+use crate::{
+    document::{format_from_path, parse_document},
+    render::{load_env, render_value},
+};
+
+#[derive(Debug, Args)]
+pub(crate) struct DiffArgs {
+    #[clap(long, short, value_parser)]
+    input: Input,
+
+    #[arg(long, short, value_parser)]
+    env: Option<Input>,
+
+    #[clap(long, short, value_parser, default_value = "-")]
+    output: Output,
+
+    /// The existing YAML, JSON or TOML file to compare against.
+    #[clap(value_parser)]
+    existing: Input,
+}
+
+/// Render a template and compare it with an existing file. Like `diff(1)`,
+/// exits with 1 if there are any differences, or 2 if there was trouble.
+pub(crate) fn diff(args: DiffArgs) -> Result<ExitCode, Box<dyn Error>> {
+    match compare(args) {
+        Ok(Some(true)) => Ok(ExitCode::SUCCESS),
+        Ok(Some(false)) => Ok(ExitCode::from(1)),
+        Ok(None) => Ok(ExitCode::from(2)),
+        Err(error) => {
+            eprintln!("Error: {error}");
+            Ok(ExitCode::from(2))
+        }
+    }
+}
+
+/// Write the differences, and return whether there were none.
+///
+/// Returns `None` if there were diagnostics, which have already been displayed.
+fn compare(mut args: DiffArgs) -> Result<Option<bool>, Box<dyn Error>> {
+    let env = match args.env.as_mut() {
+        Some(env) => load_env(env, false)?,
+        None => Environment::new(),
+    };
+
+    let Some(rendered) = render_value(&mut args.input, env)? else {
+        return Ok(None);
+    };
+
+    let mut existing = String::new();
+    args.existing.read_to_string(&mut existing)?;
+    let existing_format = format_from_path(args.existing.path().path());
+    let existing = parse_document(&existing, existing_format)?;
+
+    let changes = diff_values(&existing, &rendered);
+    for change in changes.iter() {
+        writeln!(args.output, "{}", change)?;
+    }
+
+    Ok(Some(changes.is_empty()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct KeyPath(Vec<PathSegment>);
+
+impl KeyPath {
+    fn with(&self, segment: PathSegment) -> Self {
+        let mut segments = self.0.clone();
+        segments.push(segment);
+        Self(segments)
+    }
+}
+
+impl Display for KeyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "(root)");
+        }
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) => {
+                    let is_identifier = key.chars().next().is_some_and(|c| !c.is_ascii_digit())
+                        && key.chars().all(|c| c.is_alphanumeric() || c == '_');
+                    if !is_identifier {
+                        write!(f, "[{:?}]", key)?;
+                    } else if i == 0 {
+                        write!(f, "{}", key)?;
+                    } else {
+                        write!(f, ".{}", key)?;
+                    }
+                }
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Change {
+    Added {
+        path: KeyPath,
+        value: SerdeValue,
+    },
+    Removed {
+        path: KeyPath,
+        value: SerdeValue,
+    },
+    Changed {
+        path: KeyPath,
+        from: SerdeValue,
+        to: SerdeValue,
+    },
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added { path, value } => write!(f, "+ {}: {}", path, inline(value)),
+            Change::Removed { path, value } => write!(f, "- {}: {}", path, inline(value)),
+            Change::Changed { path, from, to } => {
+                write!(f, "~ {}: {} -> {}", path, inline(from), inline(to))
+            }
+        }
+    }
+}
+
+/// Write a value on a single line, quoting strings so they can't be mistaken
+/// for other kinds of values.
+fn inline(value: &SerdeValue) -> String {
+    match value {
+        SerdeValue::String(string) => format!("{:?}", string),
+        SerdeValue::List(list) => {
+            let items: Vec<String> = list.iter().map(inline).collect();
+            format!("[{}]", items.join(", "))
+        }
+        SerdeValue::Object(object) => {
+            let entries: Vec<String> = object
+                .iter()
+                .map(|(key, value)| format!("{:?}: {}", key, inline(value)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        value => value.to_string(),
+    }
+}
+
+/// Structurally compare two values, returning the changes needed to turn
+/// `from` into `to`.
+///
+/// Objects are compared by key, regardless of order. List items are matched
+/// up by their longest common subsequence, so an insertion or removal in the
+/// middle of a list is reported as such, rather than as a change to every
+/// item after it.
+pub(crate) fn diff_values(from: &SerdeValue, to: &SerdeValue) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_at(&KeyPath::default(), from, to, &mut changes);
+    changes
+}
+
+fn diff_at(path: &KeyPath, from: &SerdeValue, to: &SerdeValue, changes: &mut Vec<Change>) {
+    match (from, to) {
+        (SerdeValue::Object(from), SerdeValue::Object(to)) => {
+            for (key, from_value) in from {
+                let path = path.with(PathSegment::Key(key.clone()));
+                match to.get(key) {
+                    Some(to_value) => diff_at(&path, from_value, to_value, changes),
+                    None => changes.push(Change::Removed {
+                        path,
+                        value: from_value.clone(),
+                    }),
+                }
+            }
+            for (key, to_value) in to {
+                if !from.contains_key(key) {
+                    changes.push(Change::Added {
+                        path: path.with(PathSegment::Key(key.clone())),
+                        value: to_value.clone(),
+                    })
+                }
+            }
+        }
+        (SerdeValue::List(from), SerdeValue::List(to)) => diff_lists(path, from, to, changes),
+        (from, to) => {
+            if from != to {
+                changes.push(Change::Changed {
+                    path: path.clone(),
+                    from: from.clone(),
+                    to: to.clone(),
+                })
+            }
+        }
+    }
+}
+
+fn diff_lists(path: &KeyPath, from: &[SerdeValue], to: &[SerdeValue], changes: &mut Vec<Change>) {
+    // lengths[i][j] is the longest common subsequence of from[i..] and to[j..]
+    let mut lengths = vec![vec![0usize; to.len() + 1]; from.len() + 1];
+    for i in (0..from.len()).rev() {
+        for j in (0..to.len()).rev() {
+            lengths[i][j] = if from[i] == to[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut matches = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < from.len() && j < to.len() {
+        if from[i] == to[j] {
+            matches.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches.push((from.len(), to.len()));
+
+    // between each pair of matched items, pair up the unmatched items as
+    // changes, and report any left over as removed or added.
+    let (mut from_start, mut to_start) = (0, 0);
+    for (from_end, to_end) in matches {
+        let removed = &from[from_start..from_end];
+        let added = &to[to_start..to_end];
+        let paired = removed.len().min(added.len());
+        for (offset, (from_item, to_item)) in removed.iter().zip(added).enumerate() {
+            let path = path.with(PathSegment::Index(to_start + offset));
+            diff_at(&path, from_item, to_item, changes);
+        }
+        for (offset, value) in removed.iter().enumerate().skip(paired) {
+            changes.push(Change::Removed {
+                path: path.with(PathSegment::Index(from_start + offset)),
+                value: value.clone(),
+            });
+        }
+        for (offset, value) in added.iter().enumerate().skip(paired) {
+            changes.push(Change::Added {
+                path: path.with(PathSegment::Index(to_start + offset)),
+                value: value.clone(),
+            });
+        }
+        from_start = from_end + 1;
+        to_start = to_end + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rimu::SerdeValue;
+
+    use std::{env::temp_dir, fs, process::ExitCode};
+
+    use clio::{Input, Output};
+
+    use super::{diff_values, DiffArgs};
+
+    fn yaml(code: &str) -> SerdeValue {
+        serde_yaml::from_str(code).unwrap()
+    }
+
+    fn diff(from: &str, to: &str) -> Vec<String> {
+        diff_values(&yaml(from), &yaml(to))
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn same() {
+        let actual = diff("{ a: 1, b: [1, 2] }", "{ b: [1, 2], a: 1 }");
+
+        assert_eq!(actual, Vec::<String>::new());
+    }
+
+    #[test]
+    fn objects() {
+        let actual = diff(
+            "services: { api: { replicas: 2, debug: true } }",
+            "services: { api: { replicas: 3, image: app } }",
+        );
+
+        let expected = vec![
+            "~ services.api.replicas: 2 -> 3",
+            "- services.api.debug: true",
+            "+ services.api.image: \"app\"",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn lists() {
+        let actual = diff(
+            "[a, b, c, { name: d, port: 80 }]",
+            "[a, x, c, { name: d, port: 8080 }, e]",
+        );
+
+        let expected = vec![
+            "~ [1]: \"b\" -> \"x\"",
+            "~ [3].port: 80 -> 8080",
+            "+ [4]: \"e\"",
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn list_insertion() {
+        let actual = diff("[a, b, c]", "[a, new, b, c]");
+
+        assert_eq!(actual, vec!["+ [1]: \"new\""]);
+    }
+
+    #[test]
+    fn kinds() {
+        let actual = diff("{ \"a b\": [1], c: null }", "{ \"a b\": { x: 1 }, c: 0 }");
+
+        let expected = vec!["~ [\"a b\"]: [1] -> {\"x\": 1}", "~ c: null -> 0"];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn exit_codes() {
+        let path =
+            |name: &str| temp_dir().join(format!("rimu-diff-{}-{}", std::process::id(), name));
+        let (input, existing, output) = (path("input.rimu"), path("existing.yaml"), path("out"));
+        fs::write(&existing, "a: 1\n").unwrap();
+
+        let run = |template: &str| {
+            fs::write(&input, template).unwrap();
+            super::diff(DiffArgs {
+                input: Input::new(&input).unwrap(),
+                env: None,
+                output: Output::new(&output).unwrap(),
+                existing: Input::new(&existing).unwrap(),
+            })
+            .unwrap()
+        };
+
+        assert_eq!(run("a: 1"), ExitCode::SUCCESS);
+        assert_eq!(run("a: 2"), ExitCode::from(1));
+        // an error rendering the template
+        assert_eq!(run("a: missing"), ExitCode::from(2));
+
+        // or reading the existing file
+        fs::write(&existing, "a: [").unwrap();
+        assert_eq!(run("a: 1"), ExitCode::from(2));
+
+        for path in [input, existing, output] {
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
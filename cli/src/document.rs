@@ -0,0 +1,179 @@
+use std::{error::Error, path::Path};
+
+use rimu::{EnvironmentError, SerdeValue, SerdeValueObject};
+use rimu_format::Format;
+use serde::Deserialize;
+
+/// Guess the format to read a file as from its extension, falling back to
+/// YAML (which is also a superset of JSON) for formats which can only be
+/// written, or aren't known.
+pub(crate) fn format_from_path(path: &Path) -> Format {
+    let format = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .and_then(|extension| extension.parse().ok());
+    match format {
+        Some(format @ (Format::Yaml | Format::Json | Format::Toml)) => format,
+        _ => Format::Yaml,
+    }
+}
+
+pub(crate) fn to_document_string(
+    value: &SerdeValue,
+    format: Format,
+) -> Result<String, Box<dyn Error>> {
+    Ok(rimu_format::to_string(value, format)?)
+}
+
+/// Render each document as part of a stream: YAML documents each start with
+/// a `---` marker, JSON documents are written one per line (JSON Lines).
+pub(crate) fn to_documents_string(
+    documents: &[SerdeValue],
+    format: Format,
+) -> Result<String, Box<dyn Error>> {
+    let mut output = String::new();
+    for document in documents {
+        match format {
+            Format::Yaml => {
+                output.push_str("---\n");
+                output.push_str(&rimu_format::to_string(document, format)?);
+            }
+            Format::Json => {
+                output.push_str(&rimu_format::to_string(document, format)?);
+                output.push('\n');
+            }
+            _ => return Err(format!("{} does not support multiple documents", format).into()),
+        }
+    }
+    Ok(output)
+}
+
+pub(crate) fn parse_document(content: &str, format: Format) -> Result<SerdeValue, Box<dyn Error>> {
+    Ok(match format {
+        Format::Yaml => serde_yaml::from_str(content)?,
+        Format::Json => serde_json::from_str(content)?,
+        Format::Toml => toml::from_str(content)?,
+        _ => return Err(format!("Reading {} is not supported", format).into()),
+    })
+}
+
+/// The inverse of [`to_documents_string`]: read a YAML document stream or
+/// JSON Lines into a list of documents.
+pub(crate) fn parse_documents(
+    content: &str,
+    format: Format,
+) -> Result<Vec<SerdeValue>, Box<dyn Error>> {
+    match format {
+        // serde_yaml reads an empty stream as one null document
+        Format::Yaml if content.trim().is_empty() => Ok(Vec::new()),
+        Format::Yaml => serde_yaml::Deserializer::from_str(content)
+            .map(|document| SerdeValue::deserialize(document).map_err(Into::into))
+            .collect(),
+        Format::Json => serde_json::Deserializer::from_str(content)
+            .into_iter::<SerdeValue>()
+            .map(|document| document.map_err(Into::into))
+            .collect(),
+        _ => Err(format!("{} does not support multiple documents", format).into()),
+    }
+}
+
+/// Merge environment documents in order, so later documents override keys
+/// set by earlier ones.
+pub(crate) fn merge_env_documents(
+    documents: Vec<SerdeValue>,
+) -> Result<SerdeValue, EnvironmentError> {
+    let mut env = SerdeValueObject::new();
+    for document in documents {
+        let SerdeValue::Object(object) = document else {
+            return Err(EnvironmentError::InvalidEnvironmentValue {
+                value: Box::new(document),
+            });
+        };
+        env.extend(object);
+    }
+    Ok(SerdeValue::Object(env))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+    use rimu_format::Format;
+
+    use rimu::SerdeValue;
+
+    use super::{format_from_path, merge_env_documents, parse_documents, to_documents_string};
+
+    fn documents() -> Vec<SerdeValue> {
+        serde_yaml::from_str(
+            "
+- kind: Service
+  metadata: { name: api }
+- kind: Deployment
+  spec: { replicas: 3, ports: [80, 443] }
+- [1, two]
+- null
+",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn yaml_stream_round_trip() {
+        let documents = documents();
+        let stream = to_documents_string(&documents, Format::Yaml).unwrap();
+        assert_eq!(stream.matches("---\n").count(), documents.len());
+        assert_eq!(parse_documents(&stream, Format::Yaml).unwrap(), documents);
+    }
+
+    #[test]
+    fn json_lines_round_trip() {
+        let documents = documents();
+        let stream = to_documents_string(&documents, Format::Json).unwrap();
+        assert_eq!(stream.lines().count(), documents.len());
+        assert_eq!(parse_documents(&stream, Format::Json).unwrap(), documents);
+    }
+
+    #[test]
+    fn stream_of_one_or_none() {
+        for format in [Format::Yaml, Format::Json] {
+            let documents = vec![SerdeValue::String("one".into())];
+            let stream = to_documents_string(&documents, format).unwrap();
+            assert_eq!(parse_documents(&stream, format).unwrap(), documents);
+
+            assert_eq!(to_documents_string(&[], format).unwrap(), "");
+            assert_eq!(parse_documents("", format).unwrap(), vec![]);
+        }
+    }
+
+    #[test]
+    fn stream_unsupported_format() {
+        assert!(to_documents_string(&documents(), Format::Toml).is_err());
+        assert!(parse_documents("a = 1", Format::Toml).is_err());
+    }
+
+    #[test]
+    fn merge_env_stream() {
+        let stream = "---\na: 1\nb: 1\n---\nb: 2\nc: 2\n";
+        let documents = parse_documents(stream, Format::Yaml).unwrap();
+        let expected: SerdeValue = serde_yaml::from_str("{ a: 1, b: 2, c: 2 }").unwrap();
+        assert_eq!(merge_env_documents(documents).unwrap(), expected);
+
+        let documents = parse_documents("{\"a\": 1}\n[1]\n", Format::Json).unwrap();
+        assert!(merge_env_documents(documents).is_err());
+    }
+
+    #[test]
+    fn format_from_path_reads_yaml_by_default() {
+        assert_eq!(format_from_path(Path::new("env.json")), Format::Json);
+        assert_eq!(format_from_path(Path::new("env.toml")), Format::Toml);
+        assert_eq!(format_from_path(Path::new("env.yml")), Format::Yaml);
+        assert_eq!(format_from_path(Path::new("env")), Format::Yaml);
+        assert_eq!(format_from_path(Path::new("env.txt")), Format::Yaml);
+        // these formats can only be written
+        for path in ["prod.env", "main.tf", "env.ini", "env.xml", "env.nix"] {
+            assert_eq!(format_from_path(Path::new(path)), Format::Yaml, "{}", path);
+        }
+    }
+}
@@ -1,104 +1,49 @@
-use std::{
-    cell::RefCell,
-    error::Error,
-    io::{Read, Write},
-    process::ExitCode,
-    rc::Rc,
-    str::FromStr,
-};
-
-use clap::Parser;
-use clio::*;
-use rimu::{evaluate, parse, Environment, ErrorReport, SerdeValue, SourceId, Value};
+mod diff;
+mod document;
+mod render;
 
-#[derive(Debug, Clone, Copy)]
-enum Format {
-    Yaml,
-    Json,
-    Toml,
-}
+use std::{error::Error, process::ExitCode};
 
-impl FromStr for Format {
-    type Err = String;
+use clap::{Parser, Subcommand};
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
-            "yaml" | "yml" => Ok(Format::Yaml),
-            "json" => Ok(Format::Json),
-            "toml" => Ok(Format::Toml),
-            _ => Err(format!("Unknown format: {}", s)),
-        }
-    }
-}
+use crate::{
+    diff::{diff, DiffArgs},
+    render::{render, RenderArgs},
+};
 
 #[derive(Debug, Parser)]
-#[clap(version)]
-struct Args {
-    #[clap(long, short, value_parser)]
-    input: Input,
-
-    #[arg(long, short, value_parser)]
-    env: Option<Input>,
-
-    #[clap(long, short, value_parser, default_value = "-")]
-    output: Output,
-
-    #[clap(long, short, default_value = "yaml")]
-    format: Format,
+#[clap(version, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// With no command, render a template.
+    #[command(flatten)]
+    render: Option<RenderArgs>,
 }
 
-fn main() -> std::result::Result<ExitCode, Box<dyn Error>> {
-    let mut args = Args::parse();
-
-    let mut input = String::new();
-    args.input.read_to_string(&mut input)?;
-    let input_source = SourceId::from_path(args.input.path().path());
-
-    let env = if let Some(mut env_arg) = args.env {
-        let mut env_string = String::new();
-        env_arg.read_to_string(&mut env_string)?;
-        let env_value: SerdeValue = env_string.into();
-        Environment::from_value(&env_value, None)?
-    } else {
-        Environment::new()
-    };
-
-    let (block, errors) = parse(input.as_str(), input_source.clone());
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Render a template.
+    Render(RenderArgs),
+    /// Render a template and compare it with an existing file.
+    Diff(DiffArgs),
+}
 
-    if !errors.is_empty() {
-        for error in errors {
-            let report: ErrorReport = error.into();
-            report.display(input.as_str(), input_source.clone());
-        }
-        return Ok(ExitCode::FAILURE);
-    }
+fn main() -> ExitCode {
+    let cli = Cli::parse();
 
-    let Some(block) = block else {
-        println!("No block.");
-        return Ok(ExitCode::FAILURE);
+    let result: Result<ExitCode, Box<dyn Error>> = match (cli.command, cli.render) {
+        (Some(Command::Render(args)), _) | (None, Some(args)) => render(args),
+        (Some(Command::Diff(args)), _) => diff(args),
+        (None, None) => unreachable!("clap requires either a command or render args"),
     };
 
-    // println!("Block: {}", block);
-
-    let env = Rc::new(RefCell::new(env));
-    let value = match evaluate(&block, env) {
-        Ok(value) => value,
+    match result {
+        Ok(code) => code,
         Err(error) => {
-            let report: ErrorReport = error.into();
-            report.display(input.as_str(), input_source.clone());
-            return Ok(ExitCode::FAILURE);
+            eprintln!("Error: {error}");
+            ExitCode::FAILURE
         }
-    };
-    let value: Value = value.into_inner();
-    let value: SerdeValue = value.into();
-
-    let output: String = match args.format {
-        Format::Yaml => serde_yaml::to_string(&value)?,
-        Format::Json => serde_json::to_string(&value)?,
-        Format::Toml => toml::to_string(&value)?,
-    };
-
-    args.output.write_all(output.as_bytes())?;
-
-    Ok(ExitCode::SUCCESS)
+    }
 }
@@ -0,0 +1,361 @@
+use std::{
+    cell::RefCell,
+    error::Error,
+    io::{Read, Write},
+    path::PathBuf,
+    process::ExitCode,
+    rc::Rc,
+    thread::sleep,
+    time::{Duration, SystemTime},
+};
+
+use clap::Args;
+use clio::{ClioPath, Input, Output};
+use rimu::{
+    evaluate, parse, parse_expression, select, Environment, ErrorReport, SerdeValue, SourceId,
+    Value,
+};
+use rimu_format::Format;
+
+use crate::document::{
+    format_from_path, merge_env_documents, parse_document, parse_documents, to_document_string,
+    to_documents_string,
+};
+
+/// How often to check watched files for changes.
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Args)]
+pub(crate) struct RenderArgs {
+    #[clap(long, short, value_parser)]
+    input: Input,
+
+    #[arg(long, short, value_parser)]
+    env: Option<Input>,
+
+    #[clap(long, short, value_parser, default_value = "-")]
+    output: Output,
+
+    /// One of: yaml, json, toml, hcl, ini, dotenv, xml, nix
+    #[clap(long, short, default_value = "yaml")]
+    format: Format,
+
+    /// Render a top-level list as a document stream (`---`-separated YAML
+    /// documents, or JSON Lines), and read `--env` as a document stream of
+    /// objects merged in order.
+    #[clap(long)]
+    multi_doc: bool,
+
+    /// Output only part of the result: a Rimu expression, evaluated with `$`
+    /// as the result and each of its top-level keys as a variable, such as
+    /// `services.api.env` or `$[0]`.
+    #[clap(long, short)]
+    select: Option<String>,
+
+    /// Re-render whenever the input or env file changes, until interrupted.
+    #[clap(long, short)]
+    watch: bool,
+}
+
+pub(crate) fn render(mut args: RenderArgs) -> Result<ExitCode, Box<dyn Error>> {
+    if args.watch {
+        return watch(args);
+    }
+
+    let rendered = render_output(
+        &mut args.input,
+        args.env.as_mut(),
+        args.select.as_deref(),
+        args.format,
+        args.multi_doc,
+    );
+    let output = match rendered {
+        Ok(Some(output)) => output,
+        Ok(None) => return Ok(ExitCode::FAILURE),
+        Err(error) => {
+            eprintln!("Error: {error}");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    args.output.write_all(output.as_bytes())?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Render the template, and then again every time a watched file changes.
+///
+/// Diagnostics are displayed without exiting, and the output is only
+/// rewritten when the rendered result is different from last time.
+fn watch(args: RenderArgs) -> Result<ExitCode, Box<dyn Error>> {
+    if args.input.is_std() || args.env.as_ref().is_some_and(|env| env.is_std()) {
+        return Err("--watch needs files to watch, not stdin".into());
+    }
+
+    let input_path = args.input.path().clone();
+    let env_path = args.env.as_ref().map(|env| env.path().clone());
+    let output_path = args.output.path().clone();
+
+    let watched: Vec<PathBuf> = std::iter::once(&input_path)
+        .chain(env_path.as_ref())
+        .map(|path| path.to_path_buf())
+        .collect();
+
+    let mut last_output: Option<String> = None;
+
+    loop {
+        let modified = modified_times(&watched);
+
+        let rendered = Input::new(input_path.clone())
+            .map_err(Into::into)
+            .and_then(|mut input| {
+                let mut env = env_path.clone().map(Input::new).transpose()?;
+                render_output(
+                    &mut input,
+                    env.as_mut(),
+                    args.select.as_deref(),
+                    args.format,
+                    args.multi_doc,
+                )
+            });
+
+        match rendered {
+            Ok(Some(output)) => {
+                write_changed(&output_path, output, &mut last_output)?;
+            }
+            Ok(None) => {}
+            Err(error) => eprintln!("Error: {error}"),
+        }
+
+        while modified_times(&watched) == modified {
+            sleep(WATCH_INTERVAL);
+        }
+    }
+}
+
+/// Write the output, unless it's the same as the last output written.
+/// Returns whether it was written.
+fn write_changed(
+    path: &ClioPath,
+    output: String,
+    last_output: &mut Option<String>,
+) -> Result<bool, Box<dyn Error>> {
+    if last_output.as_ref() == Some(&output) {
+        return Ok(false);
+    }
+    let mut writer = Output::new(path.clone())?;
+    writer.write_all(output.as_bytes())?;
+    writer.finish()?;
+    *last_output = Some(output);
+    Ok(true)
+}
+
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| path.metadata().and_then(|meta| meta.modified()).ok())
+        .collect()
+}
+
+/// Render the template to a string in the requested format.
+///
+/// Returns `None` if there were diagnostics, which have already been displayed.
+fn render_output(
+    input: &mut Input,
+    env: Option<&mut Input>,
+    selector: Option<&str>,
+    format: Format,
+    multi_doc: bool,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let env = match env {
+        Some(env) => load_env(env, multi_doc)?,
+        None => Environment::new(),
+    };
+
+    let Some(value) = render_value(input, env)? else {
+        return Ok(None);
+    };
+
+    let value = match selector {
+        Some(selector) => match select_value(value, selector) {
+            Some(value) => value,
+            None => return Ok(None),
+        },
+        None => value,
+    };
+
+    if multi_doc {
+        let SerdeValue::List(documents) = value else {
+            return Err(
+                format!("--multi-doc expects the template to render a list, got: {value}").into(),
+            );
+        };
+        Ok(Some(to_documents_string(&documents, format)?))
+    } else {
+        Ok(Some(to_document_string(&value, format)?))
+    }
+}
+
+pub(crate) fn load_env(env: &mut Input, multi_doc: bool) -> Result<Environment, Box<dyn Error>> {
+    let mut env_string = String::new();
+    env.read_to_string(&mut env_string)?;
+    let env_format = format_from_path(env.path().path());
+    let env_value = if multi_doc {
+        let documents = parse_documents(&env_string, env_format)?;
+        merge_env_documents(documents)?
+    } else {
+        parse_document(&env_string, env_format)?
+    };
+    Ok(Environment::from_value(&env_value, None)?)
+}
+
+/// Parse and evaluate the template.
+///
+/// Returns `None` if there were diagnostics, which have already been displayed.
+pub(crate) fn render_value(
+    input: &mut Input,
+    env: Environment,
+) -> Result<Option<SerdeValue>, Box<dyn Error>> {
+    let mut code = String::new();
+    input.read_to_string(&mut code)?;
+    let source = SourceId::from_path(input.path().path());
+
+    let (block, errors) = parse(code.as_str(), source.clone());
+
+    if !errors.is_empty() {
+        for error in errors {
+            let report: ErrorReport = error.into();
+            report.display(code.as_str(), source.clone());
+        }
+        return Ok(None);
+    }
+
+    let Some(block) = block else {
+        eprintln!("No block.");
+        return Ok(None);
+    };
+
+    let env = Rc::new(RefCell::new(env));
+    let value = match evaluate(&block, env) {
+        Ok(value) => value,
+        Err(error) => {
+            let report: ErrorReport = error.into();
+            report.display(code.as_str(), source.clone());
+            return Ok(None);
+        }
+    };
+    let value: Value = value.into_inner();
+    Ok(Some(value.into()))
+}
+
+/// Select part of a rendered value, see [`rimu::select`].
+///
+/// Returns `None` if there were diagnostics, which have already been displayed.
+fn select_value(value: SerdeValue, selector: &str) -> Option<SerdeValue> {
+    let source = SourceId::from("--select".to_string());
+
+    let (expression, errors) = parse_expression(selector, source.clone());
+
+    if !errors.is_empty() {
+        for error in errors {
+            let report: ErrorReport = error.into();
+            report.display(selector, source.clone());
+        }
+        return None;
+    }
+
+    let expression = expression?;
+
+    let env = Rc::new(RefCell::new(Environment::new()));
+    match select(&value.into(), &expression, env) {
+        Ok(value) => Some(value.into_inner().into()),
+        Err(error) => {
+            let report: ErrorReport = error.into();
+            report.display(selector, source);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{self, File},
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+
+    use clio::{ClioPath, Input};
+    use pretty_assertions::assert_eq;
+    use rimu_format::Format;
+
+    use super::{modified_times, render_output, write_changed};
+
+    /// A path in the temp dir, unique to this test process.
+    fn temp_path(name: &str) -> PathBuf {
+        temp_dir().join(format!("rimu-cli-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn write_changed_output() {
+        let path = temp_path("output.yaml");
+        let clio_path = ClioPath::new(&path).unwrap();
+        let mut last_output = None;
+
+        assert!(write_changed(&clio_path, "a: 1\n".into(), &mut last_output).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a: 1\n");
+
+        // the same output isn't written again
+        fs::remove_file(&path).unwrap();
+        assert!(!write_changed(&clio_path, "a: 1\n".into(), &mut last_output).unwrap());
+        assert!(!path.exists());
+
+        assert!(write_changed(&clio_path, "a: 2\n".into(), &mut last_output).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a: 2\n");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn modified_times_change() {
+        let path = temp_path("modified.rimu");
+        let missing = temp_path("missing.rimu");
+        fs::write(&path, "a: 1").unwrap();
+        let paths = vec![path.clone(), missing];
+
+        let before = modified_times(&paths);
+        assert!(before[0].is_some() && before[1].is_none());
+
+        let later = SystemTime::now() + Duration::from_secs(10);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_ne!(modified_times(&paths), before);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn render_again_after_env_change() {
+        let input_path = temp_path("input.rimu");
+        let env_path = temp_path("env.yaml");
+        fs::write(&input_path, "replicas: count").unwrap();
+
+        let render = |env: &str| {
+            fs::write(&env_path, env).unwrap();
+            let mut input = Input::new(&input_path).unwrap();
+            let mut env = Input::new(&env_path).unwrap();
+            render_output(&mut input, Some(&mut env), None, Format::Yaml, false).unwrap()
+        };
+
+        let first = render("count: 1");
+        let second = render("count: 2");
+        fs::remove_file(&input_path).unwrap();
+        fs::remove_file(&env_path).unwrap();
+
+        assert_ne!(first, second);
+        assert!(second.unwrap().contains("replicas: 2"));
+    }
+}
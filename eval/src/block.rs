@@ -3,7 +3,7 @@
 
 use std::{cell::RefCell, ops::Deref, rc::Rc};
 
-use rimu_ast::{Block, Expression, SpannedBlock, SpannedExpression};
+use rimu_ast::{Block, Expression, ListItem, ObjectEntry, SpannedBlock, SpannedExpression};
 use rimu_meta::{Span, Spanned};
 use rimu_value::{
     Environment, Function, FunctionBody, SpannedValue, Value, ValueList, ValueObject,
@@ -52,32 +52,58 @@ impl Evaluator {
         evaluate_expression(&Spanned::new(expr.clone(), span), self.env.clone())
     }
 
-    fn object(
-        &self,
-        span: Span,
-        entries: &[(Spanned<String>, SpannedBlock)],
-    ) -> Result<SpannedValue> {
+    fn object(&self, span: Span, entries: &[ObjectEntry<SpannedBlock>]) -> Result<SpannedValue> {
         let mut object = ValueObject::new();
-        for (key, value) in entries.iter() {
-            let key = key.clone().into_inner();
-            let value = self.block(value)?;
-            if value.inner() == &Value::Null {
-                continue;
-            };
-            object.insert(key, value);
+        let mut keys = common::ObjectKeys::default();
+        for entry in entries.iter() {
+            match entry {
+                ObjectEntry::Entry(key, value) => {
+                    let key = keys.literal(key.clone())?;
+                    let value = self.block(value)?;
+                    if value.inner() == &Value::Null {
+                        // so `null` can remove a key given before, as by a spread
+                        object.shift_remove(&key);
+                        continue;
+                    };
+                    object.insert(key, value);
+                }
+                ObjectEntry::Computed(key, value) => {
+                    let key = common::computed_key(evaluate_expression(key, self.env.clone())?)?;
+                    let key = keys.computed(key)?;
+                    let value = self.block(value)?;
+                    if value.inner() == &Value::Null {
+                        // so `null` can remove a key given before, as by a spread
+                        object.shift_remove(&key);
+                        continue;
+                    };
+                    object.insert(key, value);
+                }
+                ObjectEntry::Spread(other) => {
+                    let other = self.block(other)?;
+                    object.extend(common::spread_object(other)?);
+                }
+            }
         }
         let value = Value::Object(object);
         Ok(Spanned::new(value, span))
     }
 
-    fn list(&self, span: Span, items: &[SpannedBlock]) -> Result<SpannedValue> {
+    fn list(&self, span: Span, items: &[ListItem<SpannedBlock>]) -> Result<SpannedValue> {
         let mut list = ValueList::with_capacity(items.len());
         for item in items.iter() {
-            let item = self.block(item)?;
-            if item.inner() == &Value::Null {
-                continue;
-            };
-            list.push(item);
+            match item {
+                ListItem::Item(item) => {
+                    let item = self.block(item)?;
+                    if item.inner() == &Value::Null {
+                        continue;
+                    };
+                    list.push(item);
+                }
+                ListItem::Spread(other) => {
+                    let other = self.block(other)?;
+                    list.extend(common::spread_list(other)?);
+                }
+            }
         }
         let value = Value::List(list);
         Ok(Spanned::new(value, span))
@@ -262,4 +288,158 @@ zero:
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn spread() {
+        let code = "
+...base
+replicas: 3
+debug: null
+ports:
+  - ...base.ports
+  - 443
+";
+
+        let env = indexmap! {
+            "base".into() => SerdeValue::Object(indexmap! {
+                "name".into() => SerdeValue::String("api".into()),
+                "replicas".into() => SerdeValue::Number(dec!(1).into()),
+                "debug".into() => SerdeValue::Boolean(true),
+                "ports".into() => SerdeValue::List(vec![SerdeValue::Number(dec!(80).into())]),
+            }),
+        };
+        let actual = test_code(code, Some(env));
+
+        // keys keep their position, and null values remove them
+        let expected = Ok(SerdeValue::Object(indexmap! {
+            "name".into() => SerdeValue::String("api".into()),
+            "replicas".into() => SerdeValue::Number(dec!(3).into()),
+            "ports".into() => SerdeValue::List(vec![
+                SerdeValue::Number(dec!(80).into()),
+                SerdeValue::Number(dec!(443).into()),
+            ]),
+        }));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn computed_key() {
+        let code = "
+services:
+  [name]:
+    port: 80
+  [name + \"-tls\"]:
+    port: 443
+";
+
+        let env = indexmap! {
+            "name".into() => SerdeValue::String("api".into()),
+        };
+        let actual = test_code(code, Some(env));
+
+        let expected = Ok(SerdeValue::Object(indexmap! {
+            "services".into() => SerdeValue::Object(indexmap! {
+                "api".into() => SerdeValue::Object(indexmap! {
+                    "port".into() => SerdeValue::Number(dec!(80).into()),
+                }),
+                "api-tls".into() => SerdeValue::Object(indexmap! {
+                    "port".into() => SerdeValue::Number(dec!(443).into()),
+                }),
+            }),
+        }));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn computed_key_duplicate() {
+        let code = "
+api: 1
+[name]: null
+";
+
+        let env = indexmap! {
+            "name".into() => SerdeValue::String("api".into()),
+        };
+        let actual = test_code(code, Some(env));
+
+        assert!(matches!(actual, Err(EvalError::DuplicateKey { .. })));
+    }
+
+    #[test]
+    fn literal_key_repeated() {
+        let code = "
+a: 1
+b: 2
+a: 3
+";
+        let actual = test_code(code, None);
+
+        let expected = Ok(SerdeValue::Object(indexmap! {
+            "a".into() => SerdeValue::Number(dec!(3).into()),
+            "b".into() => SerdeValue::Number(dec!(2).into()),
+        }));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn for_key() {
+        let code = "
+for: 1
+loop:
+  for: { for: 2 }.for
+";
+        let actual = test_code(code, None);
+
+        let expected = Ok(SerdeValue::Object(indexmap! {
+            "for".into() => SerdeValue::Number(dec!(1).into()),
+            "loop".into() => SerdeValue::Object(indexmap! {
+                "for".into() => SerdeValue::Number(dec!(2).into()),
+            }),
+        }));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn pipe() {
+        let code = "
+total:
+  xs
+  |> ((xs) => [x * 2 for x in xs])
+  |> ((xs, y) => [...xs, y])(7)
+";
+
+        let env = indexmap! {
+            "xs".into() => SerdeValue::List(vec![
+                SerdeValue::Number(dec!(1).into()),
+                SerdeValue::Number(dec!(2).into()),
+            ]),
+        };
+        let actual = test_code(code, Some(env));
+
+        let expected = Ok(SerdeValue::Object(indexmap! {
+            "total".into() => SerdeValue::List(vec![
+                SerdeValue::Number(dec!(2).into()),
+                SerdeValue::Number(dec!(4).into()),
+                SerdeValue::Number(dec!(7).into()),
+            ]),
+        }));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn spread_type_error() {
+        let code = "
+- ...base
+";
+
+        let env = indexmap! {
+            "base".into() => SerdeValue::Object(indexmap! {}),
+        };
+        let actual = test_code(code, Some(env));
+
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+    }
 }
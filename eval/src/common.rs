@@ -1,7 +1,9 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
 
-use rimu_meta::Span;
-use rimu_value::{Environment, Function, FunctionBody, SpannedValue};
+use rimu_meta::{Span, Spanned};
+use rimu_value::{
+    Environment, Function, FunctionBody, Number, SpannedValue, Value, ValueList, ValueObject,
+};
 
 use crate::{evaluate_block, evaluate_expression, EvalError, Result};
 
@@ -43,3 +45,97 @@ pub fn call(span: Span, function: Function, args: &[SpannedValue]) -> Result<Spa
     };
     Ok(value)
 }
+
+/// The items of a list being spread (`...x`) into another list.
+pub(crate) fn spread_list(value: SpannedValue) -> Result<ValueList> {
+    let (value, span) = value.take();
+    match value {
+        Value::List(list) => Ok(list),
+        value => Err(EvalError::TypeError {
+            span,
+            expected: "list".into(),
+            got: Box::new(value.into()),
+        }),
+    }
+}
+
+/// The entries of an object being spread (`...x`) into another object.
+///
+/// Extending an object with these entries overrides the values of any keys
+/// it already has, but keeps those keys where they first appeared.
+pub(crate) fn spread_object(value: SpannedValue) -> Result<ValueObject> {
+    let (value, span) = value.take();
+    match value {
+        Value::Object(object) => Ok(object),
+        value => Err(EvalError::TypeError {
+            span,
+            expected: "object".into(),
+            got: Box::new(value.into()),
+        }),
+    }
+}
+
+/// The key of an object entry computed from an expression (`[x]: y`).
+pub(crate) fn computed_key(value: SpannedValue) -> Result<Spanned<String>> {
+    let (value, span) = value.take();
+    match value {
+        Value::String(key) => Ok(Spanned::new(key, span)),
+        value => Err(EvalError::TypeError {
+            span,
+            expected: "string".into(),
+            got: Box::new(value.into()),
+        }),
+    }
+}
+
+/// The keys given by an object's entries, to check computed keys are unique.
+///
+/// A literal key (`x: y`) may repeat another literal key, with the last one
+/// winning, but a computed key (`[x]: y`) may not repeat any key, as then
+/// which one wins depends on the data. Keys from spread objects (`...x`) are
+/// not recorded, so may be overridden.
+#[derive(Default)]
+pub(crate) struct ObjectKeys {
+    literal: HashSet<String>,
+    computed: HashSet<String>,
+}
+
+impl ObjectKeys {
+    pub(crate) fn literal(&mut self, key: Spanned<String>) -> Result<String> {
+        let (key, span) = key.take();
+        if self.computed.contains(&key) {
+            return Err(EvalError::DuplicateKey { span, key });
+        }
+        self.literal.insert(key.clone());
+        Ok(key)
+    }
+
+    pub(crate) fn computed(&mut self, key: Spanned<String>) -> Result<String> {
+        let (key, span) = key.take();
+        if self.literal.contains(&key) || !self.computed.insert(key.clone()) {
+            return Err(EvalError::DuplicateKey { span, key });
+        }
+        Ok(key)
+    }
+}
+
+/// Arithmetic on numbers, which errors rather than panics if the result
+/// overflows or divides by zero.
+pub(crate) fn arithmetic(
+    span: Span,
+    left: Number,
+    right: Number,
+    operation: fn(Number, Number) -> Option<Number>,
+) -> Result<Value> {
+    match operation(left, right) {
+        Some(number) => Ok(Value::Number(number)),
+        None => Err(EvalError::Arithmetic {
+            span,
+            message: if right.is_zero() {
+                "Division by zero".into()
+            } else {
+                "Result is too large to represent".into()
+            },
+        }),
+    }
+}
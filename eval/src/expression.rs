@@ -1,13 +1,16 @@
 // with help from
 // - https://github.com/DennisPrediger/SLAC/blob/main/src/interpreter.rs
 
-use rimu_ast::{BinaryOperator, Expression, SpannedExpression, UnaryOperator};
+use rimu_ast::{
+    BinaryOperator, ComprehensionClause, Expression, ListItem, ObjectEntry, SpannedExpression,
+    UnaryOperator,
+};
 use rimu_meta::{Span, Spanned};
 use rimu_value::{
     convert_value_object_to_serde_value_object, Environment, Function, FunctionBody, Number,
     SpannedValue, Value, ValueObject,
 };
-use std::{cell::RefCell, ops::Deref, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, ops::Deref, rc::Rc};
 
 use crate::{common, EvalError, Result};
 
@@ -47,6 +50,16 @@ impl Evaluator {
 
             Expression::Object(ref entries) => self.object(span, entries),
 
+            Expression::ListComprehension { item, clauses } => {
+                self.list_comprehension(span, item, clauses)
+            }
+
+            Expression::ObjectComprehension {
+                key,
+                value,
+                clauses,
+            } => self.object_comprehension(span, key, value, clauses),
+
             Expression::Function { ref args, ref body } => self.function(span, args, body),
 
             Expression::Identifier(var) => self.variable(span, var),
@@ -62,14 +75,40 @@ impl Evaluator {
                 ref operator,
             } => self.binary(span, left, operator, right),
 
+            Expression::Call { .. }
+            | Expression::GetIndex { .. }
+            | Expression::GetKey { .. }
+            | Expression::GetSlice { .. } => Ok(self
+                .chain(expr)?
+                .unwrap_or_else(|| Spanned::new(Value::Null, span))),
+
+            Expression::Error => Err(EvalError::ErrorExpression { span }),
+        }
+    }
+
+    /// Evaluate a call, or a get of a key, index or slice, along with the
+    /// chain of these before it. This is `None` if an optional get (`?.` or
+    /// `?[`) found nothing, which skips the rest of the chain, so `a?.b.c` is
+    /// `null` when `a` is.
+    fn chain(&self, expr: &SpannedExpression) -> Result<Option<SpannedValue>> {
+        let span = expr.span();
+        match expr.inner() {
             Expression::Call {
                 ref function,
                 ref args,
             } => self.call(span, function, args),
 
-            Expression::GetIndex { container, index } => self.get_index(span, container, index),
+            Expression::GetIndex {
+                container,
+                index,
+                optional,
+            } => self.get_index(span, container, index, *optional),
 
-            Expression::GetKey { container, key } => self.get_key(span, container, key),
+            Expression::GetKey {
+                container,
+                key,
+                optional,
+            } => self.get_key(span, container, key, *optional),
 
             Expression::GetSlice {
                 container,
@@ -82,7 +121,7 @@ impl Evaluator {
                 end.as_ref().map(|e| e.deref()),
             ),
 
-            Expression::Error => Err(EvalError::ErrorExpression { span }),
+            _ => self.expression(expr).map(Some),
         }
     }
 
@@ -140,14 +179,18 @@ impl Evaluator {
         match operator {
             BinaryOperator::And => self.boolean(span, left, right, true),
             BinaryOperator::Or => self.boolean(span, left, right, false),
+            BinaryOperator::NullCoalesce => self.null_coalesce(span, left, right),
+            BinaryOperator::Pipe => self.pipe(span, Spanned::new(left, left_span), right),
             _ => {
                 let (right, right_span) = self.expression(right)?.take();
                 let value = match operator {
                     BinaryOperator::Or => unreachable!(),
                     BinaryOperator::And => unreachable!(),
+                    BinaryOperator::NullCoalesce => unreachable!(),
+                    BinaryOperator::Pipe => unreachable!(),
                     BinaryOperator::Add => match (left.clone(), right.clone()) {
                         (Value::Number(left), Value::Number(right)) => {
-                            Ok(Value::Number(left + right))
+                            common::arithmetic(span.clone(), left, right, Number::checked_add)
                         }
                         (Value::Number(_left), right) => Err(EvalError::TypeError {
                             span: right_span,
@@ -218,7 +261,7 @@ impl Evaluator {
                     },
                     BinaryOperator::Subtract => match (left.clone(), right.clone()) {
                         (Value::Number(left), Value::Number(right)) => {
-                            Ok(Value::Number(left - right))
+                            common::arithmetic(span.clone(), left, right, Number::checked_sub)
                         }
                         (Value::Number(_left), right) => Err(EvalError::TypeError {
                             span: right_span,
@@ -233,7 +276,7 @@ impl Evaluator {
                     },
                     BinaryOperator::Multiply => match (left.clone(), right.clone()) {
                         (Value::Number(left), Value::Number(right)) => {
-                            Ok(Value::Number(left * right))
+                            common::arithmetic(span.clone(), left, right, Number::checked_mul)
                         }
                         (Value::Number(_left), right) => Err(EvalError::TypeError {
                             span: right_span,
@@ -248,7 +291,7 @@ impl Evaluator {
                     },
                     BinaryOperator::Divide => match (left.clone(), right.clone()) {
                         (Value::Number(left), Value::Number(right)) => {
-                            Ok(Value::Number(left / right))
+                            common::arithmetic(span.clone(), left, right, Number::checked_div)
                         }
                         (Value::Number(_left), right) => Err(EvalError::TypeError {
                             span: right_span,
@@ -263,7 +306,7 @@ impl Evaluator {
                     },
                     BinaryOperator::Rem => match (left.clone(), right.clone()) {
                         (Value::Number(left), Value::Number(right)) => {
-                            Ok(Value::Number(left % right))
+                            common::arithmetic(span.clone(), left, right, Number::checked_rem)
                         }
                         (Value::Number(_left), right) => Err(EvalError::TypeError {
                             span: right_span,
@@ -291,74 +334,75 @@ impl Evaluator {
                             got: Box::new(left.into()),
                         }),
                     },
-                    BinaryOperator::Greater => match (left.clone(), right.clone()) {
-                        (Value::Number(left), Value::Number(right)) => {
-                            Ok(Value::Boolean(left > right))
+                    BinaryOperator::Greater => self
+                        .compare(&left, left_span, &right, right_span)
+                        .map(|ordering| Value::Boolean(ordering.is_gt())),
+                    BinaryOperator::GreaterEqual => self
+                        .compare(&left, left_span, &right, right_span)
+                        .map(|ordering| Value::Boolean(ordering.is_ge())),
+                    BinaryOperator::Less => self
+                        .compare(&left, left_span, &right, right_span)
+                        .map(|ordering| Value::Boolean(ordering.is_lt())),
+                    BinaryOperator::LessEqual => self
+                        .compare(&left, left_span, &right, right_span)
+                        .map(|ordering| Value::Boolean(ordering.is_le())),
+                    BinaryOperator::Equal => Ok(Value::Boolean(left.semantic_eq(&right))),
+                    BinaryOperator::In => match (left.clone(), right.clone()) {
+                        // same equality as `==`
+                        (left, Value::List(list)) => Ok(Value::Boolean(
+                            list.iter().any(|item| item.inner().semantic_eq(&left)),
+                        )),
+                        (Value::String(left), Value::String(right)) => {
+                            Ok(Value::Boolean(right.contains(&left)))
                         }
-                        (Value::Number(_left), right) => Err(EvalError::TypeError {
-                            span: right_span,
-                            expected: "number".into(),
-                            got: Box::new(right.into()),
-                        }),
-                        _ => Err(EvalError::TypeError {
-                            span: left_span,
-                            expected: "number".into(),
-                            got: Box::new(left.into()),
-                        }),
-                    },
-                    BinaryOperator::GreaterEqual => match (left.clone(), right.clone()) {
-                        (Value::Number(left), Value::Number(right)) => {
-                            Ok(Value::Boolean(left >= right))
+                        (Value::String(left), Value::Object(right)) => {
+                            Ok(Value::Boolean(right.contains_key(&left)))
                         }
-                        (Value::Number(_left), right) => Err(EvalError::TypeError {
-                            span: right_span,
-                            expected: "number".into(),
-                            got: Box::new(right.into()),
-                        }),
-                        _ => Err(EvalError::TypeError {
+                        (_, Value::String(_) | Value::Object(_)) => Err(EvalError::TypeError {
                             span: left_span,
-                            expected: "number".into(),
+                            expected: "string".into(),
                             got: Box::new(left.into()),
                         }),
-                    },
-                    BinaryOperator::Less => match (left.clone(), right.clone()) {
-                        (Value::Number(left), Value::Number(right)) => {
-                            Ok(Value::Boolean(left < right))
-                        }
-                        (Value::Number(_left), right) => Err(EvalError::TypeError {
-                            span: right_span,
-                            expected: "number".into(),
-                            got: Box::new(right.into()),
-                        }),
                         _ => Err(EvalError::TypeError {
-                            span: left_span,
-                            expected: "number".into(),
-                            got: Box::new(left.into()),
-                        }),
-                    },
-                    BinaryOperator::LessEqual => match (left.clone(), right.clone()) {
-                        (Value::Number(left), Value::Number(right)) => {
-                            Ok(Value::Boolean(left <= right))
-                        }
-                        (Value::Number(_left), right) => Err(EvalError::TypeError {
                             span: right_span,
-                            expected: "number".into(),
+                            expected: "list | string | object".into(),
                             got: Box::new(right.into()),
                         }),
-                        _ => Err(EvalError::TypeError {
-                            span: left_span,
-                            expected: "number".into(),
-                            got: Box::new(left.into()),
-                        }),
                     },
-                    BinaryOperator::Equal => Ok(Value::Boolean(left == right)),
-                    BinaryOperator::NotEqual => Ok(Value::Boolean(left != right)),
+                    BinaryOperator::NotEqual => Ok(Value::Boolean(!left.semantic_eq(&right))),
                 }?;
                 Ok(Spanned::new(value, span))
             }
         }
     }
 
+    fn compare(
+        &self,
+        left: &Value,
+        left_span: Span,
+        right: &Value,
+        right_span: Span,
+    ) -> Result<Ordering> {
+        // values of different kinds are only ordered by kind, which is never
+        // what a comparison means
+        if left.type_name() != right.type_name() {
+            return Err(EvalError::TypeError {
+                span: right_span,
+                expected: left.type_name().into(),
+                got: Box::new(right.clone().into()),
+            });
+        }
+        // only functions have no order
+        left.semantic_cmp(right)
+            .ok_or_else(|| EvalError::TypeError {
+                span: left_span,
+                expected:
+                    "null | boolean | number | string | host-path | target-path | list | object"
+                        .into(),
+                got: Box::new(left.clone().into()),
+            })
+    }
+
     fn boolean(
         &self,
         span: Span,
@@ -378,11 +422,59 @@ impl Evaluator {
         Ok(Spanned::new(value, span))
     }
 
-    fn list(&self, span: Span, items: &Vec<SpannedExpression>) -> Result<SpannedValue> {
+    fn null_coalesce(
+        &self,
+        span: Span,
+        left: Value,
+        right: &SpannedExpression,
+    ) -> Result<SpannedValue> {
+        let value = match left {
+            // only evaluate `right` if `left` is null
+            Value::Null => self.expression(right)?.into_inner(),
+            left => left,
+        };
+        Ok(Spanned::new(value, span))
+    }
+
+    fn pipe(
+        &self,
+        span: Span,
+        left: SpannedValue,
+        right: &SpannedExpression,
+    ) -> Result<SpannedValue> {
+        // `x |> f(a)` calls `f(x, a)`, and `x |> f` calls `f(x)`
+        let (function, args) = match right.inner() {
+            Expression::Call { function, args } => (function.deref(), args.as_slice()),
+            _ => (right, [].as_slice()),
+        };
+
+        let Value::Function(function_value) = self.expression(function)?.into_inner() else {
+            return Err(EvalError::CallNonFunction {
+                span: function.span(),
+                expr: function.clone().into_inner(),
+            });
+        };
+
+        let args = std::iter::once(Ok(left))
+            .chain(args.iter().map(|expression| self.expression(expression)))
+            .collect::<Result<Vec<SpannedValue>>>()?;
+
+        common::call(span, function_value, &args)
+    }
+
+    fn list(&self, span: Span, items: &[ListItem<SpannedExpression>]) -> Result<SpannedValue> {
         let mut next_items = Vec::with_capacity(items.len());
         for item in items {
-            let next_item = self.expression(item)?;
-            next_items.push(next_item);
+            match item {
+                ListItem::Item(item) => {
+                    let next_item = self.expression(item)?;
+                    next_items.push(next_item);
+                }
+                ListItem::Spread(list) => {
+                    let list = self.expression(list)?;
+                    next_items.extend(common::spread_list(list)?);
+                }
+            }
         }
         let value = Value::List(next_items);
         Ok(Spanned::new(value, span))
@@ -391,18 +483,135 @@ impl Evaluator {
     fn object(
         &self,
         span: Span,
-        entries: &[(Spanned<String>, SpannedExpression)],
+        entries: &[ObjectEntry<SpannedExpression>],
     ) -> Result<SpannedValue> {
         let mut object = ValueObject::new();
-        for (key, value) in entries.iter() {
-            let key = key.clone().into_inner();
-            let value = self.expression(value)?;
-            object.insert(key, value);
+        let mut keys = common::ObjectKeys::default();
+        for entry in entries.iter() {
+            match entry {
+                ObjectEntry::Entry(key, value) => {
+                    let key = keys.literal(key.clone())?;
+                    let value = self.expression(value)?;
+                    object.insert(key, value);
+                }
+                ObjectEntry::Computed(key, value) => {
+                    let key = common::computed_key(self.expression(key)?)?;
+                    let key = keys.computed(key)?;
+                    let value = self.expression(value)?;
+                    object.insert(key, value);
+                }
+                ObjectEntry::Spread(other) => {
+                    let other = self.expression(other)?;
+                    object.extend(common::spread_object(other)?);
+                }
+            }
         }
         let value = Value::Object(object);
         Ok(Spanned::new(value, span))
     }
 
+    fn list_comprehension(
+        &self,
+        span: Span,
+        item: &SpannedExpression,
+        clauses: &[Spanned<ComprehensionClause>],
+    ) -> Result<SpannedValue> {
+        let mut list = Vec::new();
+        self.comprehension(clauses, &mut |evaluator| {
+            list.push(evaluator.expression(item)?);
+            Ok(())
+        })?;
+        let value = Value::List(list);
+        Ok(Spanned::new(value, span))
+    }
+
+    fn object_comprehension(
+        &self,
+        span: Span,
+        key: &SpannedExpression,
+        value: &SpannedExpression,
+        clauses: &[Spanned<ComprehensionClause>],
+    ) -> Result<SpannedValue> {
+        let mut object = ValueObject::new();
+        let mut keys = common::ObjectKeys::default();
+        self.comprehension(clauses, &mut |evaluator| {
+            let key = common::computed_key(evaluator.expression(key)?)?;
+            let key = keys.computed(key)?;
+            let value = evaluator.expression(value)?;
+            object.insert(key, value);
+            Ok(())
+        })?;
+        let value = Value::Object(object);
+        Ok(Spanned::new(value, span))
+    }
+
+    /// Call `body` for each combination of variables bound by the `for`
+    /// clauses which passes the `if` clauses, each time in a new scope.
+    fn comprehension(
+        &self,
+        clauses: &[Spanned<ComprehensionClause>],
+        body: &mut dyn FnMut(&Evaluator) -> Result<()>,
+    ) -> Result<()> {
+        let Some((clause, rest)) = clauses.split_first() else {
+            return body(self);
+        };
+
+        match clause.inner() {
+            ComprehensionClause::If(condition) => {
+                let condition = self.expression(condition)?.into_inner();
+                if Into::<bool>::into(condition) {
+                    self.comprehension(rest, body)?;
+                }
+            }
+            ComprehensionClause::For {
+                variables,
+                iterable,
+            } => {
+                let (iterable, iterable_span) = self.expression(iterable)?.take();
+                // (index, item) for each item of a list, or (key, value) for
+                // each entry of an object
+                let is_list = matches!(iterable, Value::List(_));
+                let entries: Vec<(SpannedValue, SpannedValue)> = match iterable {
+                    Value::List(list) => list
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, item)| {
+                            (Spanned::new(Value::Number(index.into()), item.span()), item)
+                        })
+                        .collect(),
+                    Value::Object(object) => object
+                        .into_iter()
+                        .map(|(key, value)| (Spanned::new(Value::String(key), value.span()), value))
+                        .collect(),
+                    iterable => {
+                        return Err(EvalError::TypeError {
+                            span: iterable_span,
+                            expected: "list | object".into(),
+                            got: Box::new(iterable.into()),
+                        })
+                    }
+                };
+
+                for (first, second) in entries {
+                    let mut env = Environment::new_with_parent(self.env.clone());
+                    match variables.as_slice() {
+                        // one variable is bound to the item of a list, or the key of an object
+                        [name] if is_list => env.insert(name.inner(), second),
+                        [name] => env.insert(name.inner(), first),
+                        [first_name, second_name] => {
+                            env.insert(first_name.inner(), first);
+                            env.insert(second_name.inner(), second);
+                        }
+                        _ => unreachable!("the parser allows one or two variables"),
+                    }
+                    Evaluator::new(Rc::new(RefCell::new(env))).comprehension(rest, body)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn variable(&self, span: Span, var: &str) -> Result<SpannedValue> {
         let value = self
             .env
@@ -423,8 +632,11 @@ impl Evaluator {
         span: Span,
         function: &SpannedExpression,
         args: &[SpannedExpression],
-    ) -> Result<SpannedValue> {
-        let Value::Function(function) = self.expression(function)?.into_inner() else {
+    ) -> Result<Option<SpannedValue>> {
+        let Some(function_value) = self.chain(function)? else {
+            return Ok(None);
+        };
+        let Value::Function(function) = function_value.into_inner() else {
             return Err(EvalError::CallNonFunction {
                 span: function.span(),
                 expr: function.clone().into_inner(),
@@ -436,7 +648,7 @@ impl Evaluator {
             .map(|expression| self.expression(expression))
             .collect::<Result<Vec<SpannedValue>>>()?;
 
-        common::call(span, function, &args)
+        common::call(span, function, &args).map(Some)
     }
 
     fn get_index(
@@ -444,32 +656,45 @@ impl Evaluator {
         span: Span,
         container: &SpannedExpression,
         index: &SpannedExpression,
-    ) -> Result<SpannedValue> {
-        let (container, container_span) = self.expression(container)?.take();
+        optional: bool,
+    ) -> Result<Option<SpannedValue>> {
+        let Some(container) = self.chain(container)? else {
+            return Ok(None);
+        };
+        let (container, container_span) = container.take();
         let (index, index_span) = self.expression(index)?.take();
 
         let value = match (container.clone(), index.clone()) {
+            (Value::Null, _) if optional => return Ok(None),
             (Value::List(list), index_value) => {
-                let index = get_index(container_span, index_span, index_value, list.len(), false)?;
-                list[index as usize].clone()
+                match get_index(container_span, index_span, index_value, list.len(), false) {
+                    Ok(index) => list[index].clone(),
+                    Err(EvalError::IndexOutOfBounds { .. }) if optional => return Ok(None),
+                    Err(error) => return Err(error),
+                }
             }
             (Value::String(string), index_value) => {
-                let index =
-                    get_index(container_span, index_span, index_value, string.len(), false)?;
-                let ch = string[index as usize..].chars().next().unwrap();
-                Spanned::new(Value::String(ch.into()), span.clone())
+                match get_index(container_span, index_span, index_value, string.len(), false) {
+                    Ok(index) => {
+                        let ch = string[index..].chars().next().unwrap();
+                        Spanned::new(Value::String(ch.into()), span.clone())
+                    }
+                    Err(EvalError::IndexOutOfBounds { .. }) if optional => return Ok(None),
+                    Err(error) => return Err(error),
+                }
             }
-            (Value::Object(object), Value::String(key)) => {
-                object
-                    .get(&key)
-                    .cloned()
-                    .ok_or_else(|| EvalError::KeyNotFound {
+            (Value::Object(object), Value::String(key)) => match object.get(&key) {
+                Some(value) => value.clone(),
+                None if optional => return Ok(None),
+                None => {
+                    return Err(EvalError::KeyNotFound {
                         object_span: container_span,
                         object: Box::new(convert_value_object_to_serde_value_object(object)),
                         key_span: index_span,
                         key: key.clone(),
-                    })?
-            }
+                    })
+                }
+            },
             (Value::Object(_list), _) => {
                 return Err(EvalError::TypeError {
                     span: index_span,
@@ -486,7 +711,7 @@ impl Evaluator {
             }
         };
 
-        Ok(Spanned::new(value.into_inner(), span))
+        Ok(Some(Spanned::new(value.into_inner(), span)))
     }
 
     fn get_key(
@@ -494,8 +719,16 @@ impl Evaluator {
         span: Span,
         container: &SpannedExpression,
         key: &Spanned<String>,
-    ) -> Result<SpannedValue> {
-        let (container, container_span) = self.expression(container)?.take();
+        optional: bool,
+    ) -> Result<Option<SpannedValue>> {
+        let Some(container) = self.chain(container)? else {
+            return Ok(None);
+        };
+        let (container, container_span) = container.take();
+
+        if optional && container == Value::Null {
+            return Ok(None);
+        }
 
         let Value::Object(object) = container.clone() else {
             return Err(EvalError::TypeError {
@@ -505,17 +738,20 @@ impl Evaluator {
             });
         };
 
-        let value = object
-            .get(key.inner())
-            .ok_or_else(|| EvalError::KeyNotFound {
-                object_span: container_span,
-                object: Box::new(convert_value_object_to_serde_value_object(object.clone())),
-                key: key.clone().into_inner(),
-                key_span: key.span(),
-            })
-            .cloned()?;
+        let value = match object.get(key.inner()) {
+            Some(value) => value.clone(),
+            None if optional => return Ok(None),
+            None => {
+                return Err(EvalError::KeyNotFound {
+                    object_span: container_span,
+                    object: Box::new(convert_value_object_to_serde_value_object(object.clone())),
+                    key: key.clone().into_inner(),
+                    key_span: key.span(),
+                })
+            }
+        };
 
-        Ok(Spanned::new(value.into_inner(), span))
+        Ok(Some(Spanned::new(value.into_inner(), span)))
     }
 
     fn get_slice(
@@ -524,8 +760,11 @@ impl Evaluator {
         container: &SpannedExpression,
         start: Option<&SpannedExpression>,
         end: Option<&SpannedExpression>,
-    ) -> Result<SpannedValue> {
-        let (container, container_span) = self.expression(container)?.take();
+    ) -> Result<Option<SpannedValue>> {
+        let Some(container) = self.chain(container)? else {
+            return Ok(None);
+        };
+        let (container, container_span) = container.take();
         let start = match start {
             Some(start) => Some(self.expression(start)?.take()),
             None => None,
@@ -599,7 +838,7 @@ impl Evaluator {
             }
         };
 
-        Ok(Spanned::new(value, span))
+        Ok(Some(Spanned::new(value, span)))
     }
 }
 
@@ -657,7 +896,7 @@ mod tests {
 
     use indexmap::indexmap;
     use pretty_assertions::assert_eq;
-    use rimu_ast::{BinaryOperator, Expression, SpannedExpression};
+    use rimu_ast::{BinaryOperator, Expression, ListItem, ObjectEntry, SpannedExpression};
     use rimu_meta::{SourceId, Span, Spanned};
     use rimu_parse::parse_expression;
     use rimu_value::{Environment, Function, FunctionBody, SerdeValue};
@@ -731,9 +970,9 @@ mod tests {
     fn simple_list() {
         let expr = Spanned::new(
             Expression::List(vec![
-                Spanned::new(Expression::String("hello".into()), span(1..2)),
-                Spanned::new(Expression::Boolean(true), span(3..4)),
-                Spanned::new(Expression::String("world".into()), span(5..6)),
+                ListItem::Item(Spanned::new(Expression::String("hello".into()), span(1..2))),
+                ListItem::Item(Spanned::new(Expression::Boolean(true), span(3..4))),
+                ListItem::Item(Spanned::new(Expression::String("world".into()), span(5..6))),
             ]),
             span(0..8),
         );
@@ -752,11 +991,11 @@ mod tests {
     fn simple_object() {
         let expr = Spanned::new(
             Expression::Object(vec![
-                (
+                ObjectEntry::Entry(
                     Spanned::new("a".into(), span(1..2)),
                     Spanned::new(Expression::String("hello".into()), span(3..4)),
                 ),
-                (
+                ObjectEntry::Entry(
                     Spanned::new("b".into(), span(5..6)),
                     Spanned::new(Expression::String("world".into()), span(7..8)),
                 ),
@@ -834,6 +1073,32 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn arithmetic_errors() {
+        for code in [
+            "1 / 0",
+            "1 % 0",
+            "10000000000000000 * 10000000000000000",
+            "79228162514264337593543950335 + 1",
+            "-79228162514264337593543950335 - 1",
+        ] {
+            let actual = test_code(code, None);
+            assert!(
+                matches!(actual, Err(EvalError::Arithmetic { .. })),
+                "{}: {:?}",
+                code,
+                actual
+            );
+        }
+
+        // non-terminating results are rounded to 28 significant digits
+        let actual = test_code("1 / 3", None);
+        let expected = Ok(SerdeValue::Number(
+            dec!(0.3333333333333333333333333333).into(),
+        ));
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn get_list_index() {
         let env = indexmap! {
@@ -889,6 +1154,300 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn optional_chaining() {
+        let env = indexmap! {
+            "object".into() => SerdeValue::Object(indexmap! {
+                "a".into() => SerdeValue::String("apple".into()),
+                "list".into() => SerdeValue::List(vec![SerdeValue::Null]),
+            }),
+            "missing".into() => SerdeValue::Null,
+        };
+
+        let actual = test_code("object?.a", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::String("apple".into())));
+
+        let actual = test_code("object?.b", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Null));
+
+        let actual = test_code("missing?.a?.b", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Null));
+
+        let actual = test_code("object?[\"b\"]", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Null));
+
+        let actual = test_code("object.list?[3]", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Null));
+
+        // a null or missing value skips the rest of the chain
+        for code in [
+            "missing?.a.b",
+            "missing?.a[0].b",
+            "missing?[0].a",
+            "missing?.a[1:2].b",
+            "missing?.f(1).a",
+            "object?.b.c",
+            "object.list?[3].a",
+        ] {
+            let actual = test_code(code, Some(env.clone()));
+            assert_eq!(actual, Ok(SerdeValue::Null), "{}", code);
+        }
+
+        // but a null value found after the optional get doesn't
+        let actual = test_code("object?.list[0].a", Some(env.clone()));
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+
+        let actual = test_code("object.b", Some(env));
+        assert!(matches!(actual, Err(EvalError::KeyNotFound { .. })));
+    }
+
+    #[test]
+    fn null_coalesce() {
+        let env = indexmap! {
+            "object".into() => SerdeValue::Object(indexmap! {
+                "a".into() => SerdeValue::Boolean(false),
+            }),
+        };
+
+        let actual = test_code("object?.a ?? true", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Boolean(false)));
+
+        let actual = test_code("object?.b ?? \"default\"", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::String("default".into())));
+
+        // the right side is only evaluated if the left is null
+        let actual = test_code("object ?? missing", Some(env));
+        assert!(matches!(actual, Ok(SerdeValue::Object(_))));
+    }
+
+    #[test]
+    fn structural_equality() {
+        let env = indexmap! {
+            "list".into() => SerdeValue::List(vec![SerdeValue::Number(dec!(1).into())]),
+        };
+
+        let actual = test_code("[1] == list && [[1]] == [list]", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Boolean(true)));
+
+        let actual = test_code("{ a: 1, b: [2] } == { b: [2.0], a: 1 }", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Boolean(true)));
+
+        let actual = test_code("[1] != [1, 1]", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Boolean(true)));
+
+        let actual = test_code("[1] in [list]", Some(env));
+        assert_eq!(actual, Ok(SerdeValue::Boolean(true)));
+    }
+
+    #[test]
+    fn comparison() {
+        let actual = test_code("[1, 2] < [1, 3] && [1] < [1, 0]", None);
+        assert_eq!(actual, Ok(SerdeValue::Boolean(true)));
+
+        let actual = test_code("{ a: 1 } >= { a: 1 } && false < true", None);
+        assert_eq!(actual, Ok(SerdeValue::Boolean(true)));
+
+        let actual = test_code(
+            "\"v1.10\" < \"v1.9\" && \"Z\" < \"a\" && \"é\" > \"z\"",
+            None,
+        );
+        assert_eq!(actual, Ok(SerdeValue::Boolean(true)));
+
+        let actual = test_code("((a) => a) > ((a) => a)", None);
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+
+        let actual = test_code("1 < \"2\"", None);
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+
+        let actual = test_code("null >= false", None);
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+    }
+
+    #[test]
+    fn membership() {
+        let env = indexmap! {
+            "list".into() => SerdeValue::List(vec![
+                SerdeValue::String("a".into()),
+                SerdeValue::Number(dec!(1).into()),
+            ]),
+            "object".into() => SerdeValue::Object(indexmap! {
+                "a".into() => SerdeValue::Null,
+            }),
+        };
+
+        let actual = test_code("1 in list", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Boolean(true)));
+
+        let actual = test_code("\"b\" in list", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Boolean(false)));
+
+        let actual = test_code("\"ell\" in \"hello\"", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Boolean(true)));
+
+        let actual = test_code("\"a\" in object && !(\"b\" in object)", Some(env.clone()));
+        assert_eq!(actual, Ok(SerdeValue::Boolean(true)));
+
+        let actual = test_code("1 in object", Some(env.clone()));
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+
+        let actual = test_code("1 in 2", Some(env));
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+    }
+
+    #[test]
+    fn pipe() {
+        let env = indexmap! {
+            "x".into() => SerdeValue::Number(dec!(2).into()),
+        };
+
+        let actual = test_code(
+            "x |> ((a) => a * 10) |> ((a, b) => a - b)(5)",
+            Some(env.clone()),
+        );
+        assert_eq!(actual, Ok(SerdeValue::Number(dec!(15).into())));
+
+        let actual = test_code("x |> x", Some(env));
+        assert!(matches!(actual, Err(EvalError::CallNonFunction { .. })));
+    }
+
+    #[test]
+    fn spread() {
+        let env = indexmap! {
+            "base".into() => SerdeValue::Object(indexmap! {
+                "a".into() => SerdeValue::Number(dec!(1).into()),
+                "b".into() => SerdeValue::Number(dec!(2).into()),
+            }),
+            "list".into() => SerdeValue::List(vec![SerdeValue::Number(dec!(1).into())]),
+        };
+
+        let actual = test_code("{ b: 0, ...base, a: null }", Some(env.clone()));
+        let expected = Ok(SerdeValue::Object(indexmap! {
+            "b".into() => SerdeValue::Number(dec!(2).into()),
+            "a".into() => SerdeValue::Null,
+        }));
+        assert_eq!(actual, expected);
+
+        let actual = test_code("[0, ...list, ...[]]", Some(env.clone()));
+        let expected = Ok(SerdeValue::List(vec![
+            SerdeValue::Number(dec!(0).into()),
+            SerdeValue::Number(dec!(1).into()),
+        ]));
+        assert_eq!(actual, expected);
+
+        let actual = test_code("{ ...list }", Some(env.clone()));
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+
+        let actual = test_code("[...base]", Some(env));
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+    }
+
+    #[test]
+    fn computed_key() {
+        let env = indexmap! {
+            "name".into() => SerdeValue::String("api".into()),
+            "port".into() => SerdeValue::Number(dec!(80).into()),
+        };
+
+        let actual = test_code(
+            "{ [name]: port, [name + \"-tls\"]: 443 }",
+            Some(env.clone()),
+        );
+        let expected = Ok(SerdeValue::Object(indexmap! {
+            "api".into() => SerdeValue::Number(dec!(80).into()),
+            "api-tls".into() => SerdeValue::Number(dec!(443).into()),
+        }));
+        assert_eq!(actual, expected);
+
+        let actual = test_code("{ [port]: name }", Some(env.clone()));
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+
+        let actual = test_code("{ api: 1, [name]: 2 }", Some(env.clone()));
+        assert!(matches!(actual, Err(EvalError::DuplicateKey { .. })));
+
+        let actual = test_code("{ [name]: 1, [\"api\"]: 2 }", Some(env.clone()));
+        assert!(matches!(actual, Err(EvalError::DuplicateKey { .. })));
+
+        // a repeated literal key is fine, with the last one winning
+        let actual = test_code("{ a: 1, a: 2 }", Some(env));
+        let expected = Ok(SerdeValue::Object(indexmap! {
+            "a".into() => SerdeValue::Number(dec!(2).into()),
+        }));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn list_comprehension() {
+        let env = indexmap! {
+            "xs".into() => SerdeValue::List(vec![
+                SerdeValue::Number(dec!(1).into()),
+                SerdeValue::Number(dec!(2).into()),
+                SerdeValue::Number(dec!(3).into()),
+            ]),
+        };
+
+        let actual = test_code("[x * 2 for x in xs if x > 1]", Some(env.clone()));
+        let expected = Ok(SerdeValue::List(vec![
+            SerdeValue::Number(dec!(4).into()),
+            SerdeValue::Number(dec!(6).into()),
+        ]));
+        assert_eq!(actual, expected);
+
+        let actual = test_code("[i + x for i, x in xs if i == 0]", Some(env.clone()));
+        let expected = Ok(SerdeValue::List(vec![SerdeValue::Number(dec!(1).into())]));
+        assert_eq!(actual, expected);
+
+        let actual = test_code(
+            "[[x, y] for x in xs if x < 3 for y in [x] if y > 1]",
+            Some(env),
+        );
+        let expected = Ok(SerdeValue::List(vec![SerdeValue::List(vec![
+            SerdeValue::Number(dec!(2).into()),
+            SerdeValue::Number(dec!(2).into()),
+        ])]));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn object_comprehension() {
+        let env = indexmap! {
+            "object".into() => SerdeValue::Object(indexmap! {
+                "a".into() => SerdeValue::Number(dec!(1).into()),
+                "b".into() => SerdeValue::Number(dec!(2).into()),
+            }),
+            "x".into() => SerdeValue::String("outer".into()),
+        };
+
+        let actual = test_code(
+            "{ (k + k): v * 10 for k, v in object if v > 1 }",
+            Some(env.clone()),
+        );
+        let expected = Ok(SerdeValue::Object(indexmap! {
+            "bb".into() => SerdeValue::Number(dec!(20).into()),
+        }));
+        assert_eq!(actual, expected);
+
+        // variables are scoped to the comprehension
+        let actual = test_code("[[x for x in object], x]", Some(env.clone()));
+        let expected = Ok(SerdeValue::List(vec![
+            SerdeValue::List(vec![
+                SerdeValue::String("a".into()),
+                SerdeValue::String("b".into()),
+            ]),
+            SerdeValue::String("outer".into()),
+        ]));
+        assert_eq!(actual, expected);
+
+        let actual = test_code("{ (v): k for k, v in object }", Some(env.clone()));
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+
+        // keys are computed, so like computed keys each may only be produced once
+        let actual = test_code("{ (\"k\"): v for k, v in object }", Some(env.clone()));
+        assert!(matches!(actual, Err(EvalError::DuplicateKey { .. })));
+
+        let actual = test_code("[x for x in 1]", Some(env));
+        assert!(matches!(actual, Err(EvalError::TypeError { .. })));
+    }
+
     #[test]
     fn get_slice_start_end() {
         let env = indexmap! {
@@ -0,0 +1,122 @@
+use rimu_value::SerdeValue;
+
+use crate::{key_path, shape, unsupported, Format, FormatError};
+
+/// Write a flat object of scalars as `KEY=value` lines.
+pub(crate) fn to_string(value: &SerdeValue) -> Result<String, FormatError> {
+    let SerdeValue::Object(object) = value else {
+        return Err(unsupported(Format::Dotenv, shape(value), ""));
+    };
+
+    let mut output = String::new();
+    for (key, value) in object {
+        let path = key_path("", key);
+        if !is_variable_name(key) {
+            return Err(unsupported(
+                Format::Dotenv,
+                format!("the variable name {:?}", key),
+                &path,
+            ));
+        }
+        output.push_str(key);
+        output.push('=');
+        match value {
+            SerdeValue::Null => {}
+            SerdeValue::Boolean(boolean) => output.push_str(&boolean.to_string()),
+            SerdeValue::Number(number) => output.push_str(&number.to_string()),
+            SerdeValue::String(string) => write_string(&mut output, string),
+            SerdeValue::Function(_) | SerdeValue::List(_) | SerdeValue::Object(_) => {
+                return Err(unsupported(Format::Dotenv, shape(value), &path))
+            }
+        }
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+fn is_variable_name(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn write_string(output: &mut String, string: &str) {
+    let is_bare = string
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "_-./:@%+,".contains(c));
+    if is_bare {
+        output.push_str(string);
+        return;
+    }
+    output.push('"');
+    for c in string.chars() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '"' => output.push_str("\\\""),
+            '$' => output.push_str("\\$"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::indexmap;
+    use pretty_assertions::assert_eq;
+    use rimu_value::SerdeValue;
+    use rust_decimal_macros::dec;
+
+    use super::to_string;
+
+    #[test]
+    fn scalars() {
+        let value = SerdeValue::Object(indexmap! {
+            "PORT".into() => SerdeValue::Number(dec!(8080).into()),
+            "DEBUG".into() => SerdeValue::Boolean(false),
+            "HOST".into() => SerdeValue::String("example.com".into()),
+            "GREETING".into() => SerdeValue::String("hello \"$USER\"\nbye".into()),
+            "EMPTY".into() => SerdeValue::Null,
+        });
+
+        let actual = to_string(&value).unwrap();
+
+        let expected = r#"PORT=8080
+DEBUG=false
+HOST=example.com
+GREETING="hello \"\$USER\"\nbye"
+EMPTY=
+"#;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rejects_nested_values() {
+        let value = SerdeValue::Object(indexmap! {
+            "DATABASE".into() => SerdeValue::Object(indexmap! {}),
+        });
+
+        let actual = to_string(&value).unwrap_err().to_string();
+
+        assert_eq!(actual, "dotenv cannot represent an object at `DATABASE`");
+    }
+
+    #[test]
+    fn rejects_invalid_names() {
+        let value = SerdeValue::Object(indexmap! {
+            "my-var".into() => SerdeValue::Boolean(true),
+        });
+
+        let actual = to_string(&value).unwrap_err().to_string();
+
+        assert_eq!(
+            actual,
+            "dotenv cannot represent the variable name \"my-var\" at `my-var`"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use rimu_value::SerdeValue;
+
+use crate::{index_path, key_path, shape, unsupported, Format, FormatError};
+
+/// Write an object as HCL attributes, as used by `.tfvars` files.
+///
+/// Nested objects are written as object expressions (`key = { ... }`), never
+/// as blocks, since blocks can't be told apart from objects without a schema.
+pub(crate) fn to_string(value: &SerdeValue) -> Result<String, FormatError> {
+    let SerdeValue::Object(object) = value else {
+        return Err(unsupported(Format::Hcl, shape(value), ""));
+    };
+
+    let mut output = String::new();
+    for (key, value) in object {
+        let path = key_path("", key);
+        if !is_identifier(key) {
+            return Err(unsupported(
+                Format::Hcl,
+                format!("the attribute name {:?}", key),
+                &path,
+            ));
+        }
+        output.push_str(key);
+        output.push_str(" = ");
+        write_value(&mut output, value, 0, &path)?;
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+fn write_value(
+    output: &mut String,
+    value: &SerdeValue,
+    indent: usize,
+    path: &str,
+) -> Result<(), FormatError> {
+    match value {
+        SerdeValue::Null => output.push_str("null"),
+        SerdeValue::Boolean(boolean) => output.push_str(&boolean.to_string()),
+        SerdeValue::Number(number) => output.push_str(&number.to_string()),
+        SerdeValue::String(string) => write_string(output, string),
+        SerdeValue::Function(_) => return Err(unsupported(Format::Hcl, shape(value), path)),
+        SerdeValue::List(list) => {
+            if list.is_empty() {
+                output.push_str("[]");
+                return Ok(());
+            }
+            output.push_str("[\n");
+            for (index, item) in list.iter().enumerate() {
+                write_indent(output, indent + 1);
+                write_value(output, item, indent + 1, &index_path(path, index))?;
+                output.push_str(",\n");
+            }
+            write_indent(output, indent);
+            output.push(']');
+        }
+        SerdeValue::Object(object) => {
+            if object.is_empty() {
+                output.push_str("{}");
+                return Ok(());
+            }
+            output.push_str("{\n");
+            for (key, value) in object {
+                write_indent(output, indent + 1);
+                if is_identifier(key) {
+                    output.push_str(key);
+                } else {
+                    write_string(output, key);
+                }
+                output.push_str(" = ");
+                write_value(output, value, indent + 1, &key_path(path, key))?;
+                output.push('\n');
+            }
+            write_indent(output, indent);
+            output.push('}');
+        }
+    }
+    Ok(())
+}
+
+fn write_indent(output: &mut String, indent: usize) {
+    output.push_str(&"  ".repeat(indent));
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+fn write_string(output: &mut String, string: &str) {
+    output.push('"');
+    let mut chars = string.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '"' => output.push_str("\\\""),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            // escape template sequences, so strings are always literal
+            '$' | '%' if chars.peek() == Some(&'{') => {
+                output.push(c);
+                output.push(c);
+            }
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::indexmap;
+    use pretty_assertions::assert_eq;
+    use rimu_value::SerdeValue;
+    use rust_decimal_macros::dec;
+
+    use super::to_string;
+
+    #[test]
+    fn attributes() {
+        let value = SerdeValue::Object(indexmap! {
+            "region".into() => SerdeValue::String("ap-southeast-2".into()),
+            "instance_count".into() => SerdeValue::Number(dec!(3).into()),
+            "zones".into() => SerdeValue::List(vec![
+                SerdeValue::String("a".into()),
+                SerdeValue::String("b".into()),
+            ]),
+            "tags".into() => SerdeValue::Object(indexmap! {
+                "Name".into() => SerdeValue::String("web-${env}".into()),
+                "cost-center".into() => SerdeValue::Null,
+                "kubernetes.io/role".into() => SerdeValue::Boolean(true),
+            }),
+            "empty".into() => SerdeValue::List(vec![]),
+        });
+
+        let actual = to_string(&value).unwrap();
+
+        let expected = r#"region = "ap-southeast-2"
+instance_count = 3
+zones = [
+  "a",
+  "b",
+]
+tags = {
+  Name = "web-$${env}"
+  cost-center = null
+  "kubernetes.io/role" = true
+}
+empty = []
+"#;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rejects_top_level_list() {
+        let value = SerdeValue::List(vec![]);
+
+        let actual = to_string(&value).unwrap_err().to_string();
+
+        assert_eq!(actual, "HCL cannot represent a list at the top level");
+    }
+}
@@ -0,0 +1,170 @@
+use rimu_value::{SerdeValue, SerdeValueObject};
+
+use crate::{index_path, key_path, shape, unsupported, Format, FormatError};
+
+/// Write an object as INI: scalar entries become global `key=value` lines and
+/// object entries become `[section]`s.
+///
+/// A list of scalars is written as a repeated key, as in systemd unit files.
+/// Global entries are always written before any section.
+pub(crate) fn to_string(value: &SerdeValue) -> Result<String, FormatError> {
+    let SerdeValue::Object(object) = value else {
+        return Err(unsupported(Format::Ini, shape(value), ""));
+    };
+
+    let mut output = String::new();
+    let mut sections: Vec<(&String, &SerdeValueObject)> = Vec::new();
+
+    for (key, value) in object {
+        match value {
+            SerdeValue::Object(section) => sections.push((key, section)),
+            _ => write_entry(&mut output, key, value, "")?,
+        }
+    }
+
+    for (name, section) in sections {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        if name.is_empty() || name.contains([']', '\n', '\r']) {
+            return Err(unsupported(
+                Format::Ini,
+                format!("the section name {:?}", name),
+                name,
+            ));
+        }
+        output.push('[');
+        output.push_str(name);
+        output.push_str("]\n");
+        for (key, value) in section {
+            write_entry(&mut output, key, value, name)?;
+        }
+    }
+
+    Ok(output)
+}
+
+fn write_entry(
+    output: &mut String,
+    key: &str,
+    value: &SerdeValue,
+    path: &str,
+) -> Result<(), FormatError> {
+    let path = key_path(path, key);
+    if key.is_empty() || key.starts_with('[') || key.contains(['=', '\n', '\r']) {
+        return Err(unsupported(
+            Format::Ini,
+            format!("the key {:?}", key),
+            &path,
+        ));
+    }
+    match value {
+        SerdeValue::List(list) => {
+            for (index, item) in list.iter().enumerate() {
+                let path = index_path(&path, index);
+                if let SerdeValue::List(_) = item {
+                    return Err(unsupported(Format::Ini, "a nested list", &path));
+                }
+                write_line(output, key, item, &path)?;
+            }
+        }
+        SerdeValue::Object(_) => {
+            return Err(unsupported(Format::Ini, "a nested object", &path));
+        }
+        _ => write_line(output, key, value, &path)?,
+    }
+    Ok(())
+}
+
+fn write_line(
+    output: &mut String,
+    key: &str,
+    value: &SerdeValue,
+    path: &str,
+) -> Result<(), FormatError> {
+    output.push_str(key);
+    output.push('=');
+    match value {
+        SerdeValue::Null => {}
+        SerdeValue::Boolean(boolean) => output.push_str(&boolean.to_string()),
+        SerdeValue::Number(number) => output.push_str(&number.to_string()),
+        SerdeValue::String(string) => {
+            if string.contains(['\n', '\r']) {
+                return Err(unsupported(Format::Ini, "a multi-line string", path));
+            }
+            output.push_str(string)
+        }
+        SerdeValue::Function(_) | SerdeValue::List(_) | SerdeValue::Object(_) => {
+            return Err(unsupported(Format::Ini, shape(value), path));
+        }
+    }
+    output.push('\n');
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::indexmap;
+    use pretty_assertions::assert_eq;
+    use rimu_value::SerdeValue;
+    use rust_decimal_macros::dec;
+
+    use super::to_string;
+
+    #[test]
+    fn sections() {
+        let value = SerdeValue::Object(indexmap! {
+            "Unit".into() => SerdeValue::Object(indexmap! {
+                "Description".into() => SerdeValue::String("My service".into()),
+            }),
+            "Service".into() => SerdeValue::Object(indexmap! {
+                "ExecStartPre".into() => SerdeValue::List(vec![
+                    SerdeValue::String("/bin/mkdir -p /run/app".into()),
+                    SerdeValue::String("/bin/chown app /run/app".into()),
+                ]),
+                "Restart".into() => SerdeValue::String("always".into()),
+                "RestartSec".into() => SerdeValue::Number(dec!(5).into()),
+            }),
+            "version".into() => SerdeValue::Number(dec!(2).into()),
+        });
+
+        let actual = to_string(&value).unwrap();
+
+        let expected = "version=2
+
+[Unit]
+Description=My service
+
+[Service]
+ExecStartPre=/bin/mkdir -p /run/app
+ExecStartPre=/bin/chown app /run/app
+Restart=always
+RestartSec=5
+";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rejects_nested_sections() {
+        let value = SerdeValue::Object(indexmap! {
+            "a".into() => SerdeValue::Object(indexmap! {
+                "b".into() => SerdeValue::Object(indexmap! {}),
+            }),
+        });
+
+        let actual = to_string(&value).unwrap_err().to_string();
+
+        assert_eq!(actual, "INI cannot represent a nested object at `a.b`");
+    }
+
+    #[test]
+    fn rejects_multi_line_strings() {
+        let value = SerdeValue::Object(indexmap! {
+            "motd".into() => SerdeValue::String("hello\nworld".into()),
+        });
+
+        let actual = to_string(&value).unwrap_err().to_string();
+
+        assert_eq!(actual, "INI cannot represent a multi-line string at `motd`");
+    }
+}
@@ -0,0 +1,167 @@
+mod dotenv;
+mod hcl;
+mod ini;
+mod nix;
+mod xml;
+
+use std::{fmt::Display, str::FromStr};
+
+use rimu_value::SerdeValue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Yaml,
+    Json,
+    Toml,
+    Hcl,
+    Ini,
+    Dotenv,
+    Xml,
+    Nix,
+}
+
+impl FromStr for Format {
+    type Err = FormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "yaml" | "yml" => Ok(Format::Yaml),
+            "json" => Ok(Format::Json),
+            "toml" => Ok(Format::Toml),
+            "hcl" | "tf" | "tfvars" => Ok(Format::Hcl),
+            "ini" => Ok(Format::Ini),
+            "dotenv" | "env" => Ok(Format::Dotenv),
+            "xml" => Ok(Format::Xml),
+            "nix" => Ok(Format::Nix),
+            _ => Err(FormatError::UnknownFormat(s.to_owned())),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Yaml => write!(f, "YAML"),
+            Format::Json => write!(f, "JSON"),
+            Format::Toml => write!(f, "TOML"),
+            Format::Hcl => write!(f, "HCL"),
+            Format::Ini => write!(f, "INI"),
+            Format::Dotenv => write!(f, "dotenv"),
+            Format::Xml => write!(f, "XML"),
+            Format::Nix => write!(f, "Nix"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FormatError {
+    #[error("Unknown format: {0}")]
+    UnknownFormat(String),
+
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Toml(#[from] toml::ser::Error),
+
+    #[error("{format} cannot represent {shape} at {}", display_path(.path))]
+    Unsupported {
+        format: Format,
+        shape: String,
+        path: String,
+    },
+}
+
+fn display_path(path: &str) -> String {
+    if path.is_empty() {
+        "the top level".into()
+    } else {
+        format!("`{}`", path)
+    }
+}
+
+/// Serialize a value in the given format, as compactly as the format allows.
+pub fn to_string(value: &SerdeValue, format: Format) -> Result<String, FormatError> {
+    match format {
+        Format::Json => Ok(serde_json::to_string(value)?),
+        Format::Toml => Ok(toml::to_string(value)?),
+        _ => to_string_pretty(value, format),
+    }
+}
+
+/// Serialize a value in the given format, in a human-readable layout.
+pub fn to_string_pretty(value: &SerdeValue, format: Format) -> Result<String, FormatError> {
+    match format {
+        Format::Yaml => Ok(serde_yaml::to_string(value)?),
+        Format::Json => Ok(serde_json::to_string_pretty(value)?),
+        Format::Toml => Ok(toml::to_string_pretty(value)?),
+        Format::Hcl => hcl::to_string(value),
+        Format::Ini => ini::to_string(value),
+        Format::Dotenv => dotenv::to_string(value),
+        Format::Xml => xml::to_string(value),
+        Format::Nix => nix::to_string(value),
+    }
+}
+
+pub(crate) fn unsupported(format: Format, shape: impl Into<String>, path: &str) -> FormatError {
+    FormatError::Unsupported {
+        format,
+        shape: shape.into(),
+        path: path.to_owned(),
+    }
+}
+
+pub(crate) fn shape(value: &SerdeValue) -> &'static str {
+    match value {
+        SerdeValue::Null => "null",
+        SerdeValue::Boolean(_) => "a boolean",
+        SerdeValue::String(_) => "a string",
+        SerdeValue::Number(_) => "a number",
+        SerdeValue::Function(_) => "a function",
+        SerdeValue::List(_) => "a list",
+        SerdeValue::Object(_) => "an object",
+    }
+}
+
+pub(crate) fn key_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+pub(crate) fn index_path(path: &str, index: usize) -> String {
+    format!("{}[{}]", path, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::indexmap;
+    use pretty_assertions::assert_eq;
+    use rimu_value::SerdeValue;
+
+    use super::{to_string, Format};
+
+    #[test]
+    fn format_from_str() {
+        assert_eq!("yml".parse::<Format>().unwrap(), Format::Yaml);
+        assert_eq!("tfvars".parse::<Format>().unwrap(), Format::Hcl);
+        assert_eq!("env".parse::<Format>().unwrap(), Format::Dotenv);
+        assert!("csv".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn unsupported_error_message() {
+        let value = SerdeValue::Object(indexmap! {
+            "HOSTS".into() => SerdeValue::List(vec![SerdeValue::List(vec![])]),
+        });
+
+        let actual = to_string(&value, Format::Dotenv).unwrap_err().to_string();
+
+        assert_eq!(actual, "dotenv cannot represent a list at `HOSTS`");
+    }
+}
@@ -0,0 +1,144 @@
+use rimu_value::SerdeValue;
+
+use crate::{index_path, key_path, shape, unsupported, Format, FormatError};
+
+const KEYWORDS: [&str; 10] = [
+    "assert", "else", "if", "in", "inherit", "let", "or", "rec", "then", "with",
+];
+
+/// Write any value (except functions) as a Nix expression.
+pub(crate) fn to_string(value: &SerdeValue) -> Result<String, FormatError> {
+    let mut output = String::new();
+    write_value(&mut output, value, 0, "")?;
+    output.push('\n');
+    Ok(output)
+}
+
+fn write_value(
+    output: &mut String,
+    value: &SerdeValue,
+    indent: usize,
+    path: &str,
+) -> Result<(), FormatError> {
+    match value {
+        SerdeValue::Null => output.push_str("null"),
+        SerdeValue::Boolean(boolean) => output.push_str(&boolean.to_string()),
+        SerdeValue::Number(number) => output.push_str(&number.to_string()),
+        SerdeValue::String(string) => write_string(output, string),
+        SerdeValue::Function(_) => return Err(unsupported(Format::Nix, shape(value), path)),
+        SerdeValue::List(list) => {
+            if list.is_empty() {
+                output.push_str("[ ]");
+                return Ok(());
+            }
+            output.push_str("[\n");
+            for (index, item) in list.iter().enumerate() {
+                write_indent(output, indent + 1);
+                // list items are separated by whitespace, so `[ 1 -1 ]` would
+                // be read as a subtraction.
+                let is_negative =
+                    matches!(item, SerdeValue::Number(number) if number.is_sign_negative());
+                if is_negative {
+                    output.push('(');
+                }
+                write_value(output, item, indent + 1, &index_path(path, index))?;
+                if is_negative {
+                    output.push(')');
+                }
+                output.push('\n');
+            }
+            write_indent(output, indent);
+            output.push(']');
+        }
+        SerdeValue::Object(object) => {
+            if object.is_empty() {
+                output.push_str("{ }");
+                return Ok(());
+            }
+            output.push_str("{\n");
+            for (key, value) in object {
+                write_indent(output, indent + 1);
+                if is_identifier(key) {
+                    output.push_str(key);
+                } else {
+                    write_string(output, key);
+                }
+                output.push_str(" = ");
+                write_value(output, value, indent + 1, &key_path(path, key))?;
+                output.push_str(";\n");
+            }
+            write_indent(output, indent);
+            output.push('}');
+        }
+    }
+    Ok(())
+}
+
+fn write_indent(output: &mut String, indent: usize) {
+    output.push_str(&"  ".repeat(indent));
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || "_'-".contains(c))
+        && !KEYWORDS.contains(&key)
+}
+
+fn write_string(output: &mut String, string: &str) {
+    output.push('"');
+    let mut chars = string.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '"' => output.push_str("\\\""),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '$' if chars.peek() == Some(&'{') => output.push_str("\\$"),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::indexmap;
+    use pretty_assertions::assert_eq;
+    use rimu_value::SerdeValue;
+    use rust_decimal_macros::dec;
+
+    use super::to_string;
+
+    #[test]
+    fn attribute_set() {
+        let value = SerdeValue::Object(indexmap! {
+            "enable".into() => SerdeValue::Boolean(true),
+            "ports".into() => SerdeValue::List(vec![
+                SerdeValue::Number(dec!(80).into()),
+                SerdeValue::Number(dec!(-1).into()),
+            ]),
+            "home-dir".into() => SerdeValue::String("${HOME}/app".into()),
+            "in".into() => SerdeValue::Null,
+            "extra".into() => SerdeValue::Object(indexmap! {}),
+        });
+
+        let actual = to_string(&value).unwrap();
+
+        let expected = r#"{
+  enable = true;
+  ports = [
+    80
+    (-1)
+  ];
+  home-dir = "\${HOME}/app";
+  "in" = null;
+  extra = { };
+}
+"#;
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,297 @@
+use rimu_value::SerdeValue;
+
+use crate::{index_path, key_path, shape, unsupported, Format, FormatError};
+
+/// Write an object with a single key as an XML document, where the key is the
+/// root element.
+///
+/// Object keys become child elements, except keys starting with `@` which
+/// become attributes and the key `#text` which becomes text content. A list
+/// becomes repeated elements with the same name.
+pub(crate) fn to_string(value: &SerdeValue) -> Result<String, FormatError> {
+    let root = match value {
+        SerdeValue::Object(object) if object.len() == 1 => object.first(),
+        SerdeValue::Object(_) => None,
+        _ => return Err(unsupported(Format::Xml, shape(value), "")),
+    };
+    let Some((name, value)) = root else {
+        return Err(unsupported(
+            Format::Xml,
+            "an object without exactly one root element",
+            "",
+        ));
+    };
+    if let SerdeValue::List(_) = value {
+        return Err(unsupported(Format::Xml, "more than one root element", name));
+    }
+
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    write_element(&mut output, name, value, 0, name)?;
+    Ok(output)
+}
+
+fn write_element(
+    output: &mut String,
+    name: &str,
+    value: &SerdeValue,
+    indent: usize,
+    path: &str,
+) -> Result<(), FormatError> {
+    if !is_name(name) || is_namespace_declaration(name) {
+        return Err(unsupported(
+            Format::Xml,
+            format!("the element name {:?}", name),
+            path,
+        ));
+    }
+
+    write_indent(output, indent);
+    output.push('<');
+    output.push_str(name);
+
+    match value {
+        SerdeValue::Null => output.push_str("/>\n"),
+        SerdeValue::Boolean(_) | SerdeValue::Number(_) | SerdeValue::String(_) => {
+            output.push('>');
+            write_text(output, value);
+            output.push_str("</");
+            output.push_str(name);
+            output.push_str(">\n");
+        }
+        SerdeValue::Function(_) => return Err(unsupported(Format::Xml, shape(value), path)),
+        SerdeValue::List(_) => return Err(unsupported(Format::Xml, "a nested list", path)),
+        SerdeValue::Object(object) => {
+            let mut text = None;
+            let mut children = Vec::new();
+            for (key, value) in object {
+                let path = key_path(path, key);
+                if let Some(attribute) = key.strip_prefix('@') {
+                    if !is_name(attribute) {
+                        return Err(unsupported(
+                            Format::Xml,
+                            format!("the attribute name {:?}", attribute),
+                            &path,
+                        ));
+                    }
+                    if !is_scalar(value) {
+                        return Err(unsupported(
+                            Format::Xml,
+                            format!("{} as an attribute", shape(value)),
+                            &path,
+                        ));
+                    }
+                    output.push(' ');
+                    output.push_str(attribute);
+                    output.push_str("=\"");
+                    write_attribute(output, value);
+                    output.push('"');
+                } else if key == "#text" {
+                    if !is_scalar(value) {
+                        return Err(unsupported(
+                            Format::Xml,
+                            format!("{} as text", shape(value)),
+                            &path,
+                        ));
+                    }
+                    text = Some(value);
+                } else {
+                    children.push((key, value, path));
+                }
+            }
+
+            match (text, children.is_empty()) {
+                (None, true) => output.push_str("/>\n"),
+                (Some(text), true) => {
+                    output.push('>');
+                    write_text(output, text);
+                    output.push_str("</");
+                    output.push_str(name);
+                    output.push_str(">\n");
+                }
+                (text, false) => {
+                    output.push_str(">\n");
+                    if let Some(text) = text {
+                        write_indent(output, indent + 1);
+                        write_text(output, text);
+                        output.push('\n');
+                    }
+                    for (key, value, path) in children {
+                        match value {
+                            SerdeValue::List(list) => {
+                                for (index, item) in list.iter().enumerate() {
+                                    let path = index_path(&path, index);
+                                    write_element(output, key, item, indent + 1, &path)?;
+                                }
+                            }
+                            _ => write_element(output, key, value, indent + 1, &path)?,
+                        }
+                    }
+                    write_indent(output, indent);
+                    output.push_str("</");
+                    output.push_str(name);
+                    output.push_str(">\n");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_indent(output: &mut String, indent: usize) {
+    output.push_str(&"  ".repeat(indent));
+}
+
+fn is_scalar(value: &SerdeValue) -> bool {
+    matches!(
+        value,
+        SerdeValue::Null | SerdeValue::Boolean(_) | SerdeValue::Number(_) | SerdeValue::String(_)
+    )
+}
+
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_alphanumeric() || "_:.-".contains(c))
+}
+
+/// Names with the `xmlns` prefix declare namespaces, so may only be attributes.
+fn is_namespace_declaration(name: &str) -> bool {
+    name == "xmlns" || name.starts_with("xmlns:")
+}
+
+fn scalar_to_string(value: &SerdeValue) -> String {
+    match value {
+        SerdeValue::String(string) => string.clone(),
+        SerdeValue::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+fn write_text(output: &mut String, value: &SerdeValue) {
+    for c in scalar_to_string(value).chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            c => output.push(c),
+        }
+    }
+}
+
+fn write_attribute(output: &mut String, value: &SerdeValue) {
+    for c in scalar_to_string(value).chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\n' => output.push_str("&#10;"),
+            c => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::indexmap;
+    use pretty_assertions::assert_eq;
+    use rimu_value::SerdeValue;
+    use rust_decimal_macros::dec;
+
+    use super::to_string;
+
+    #[test]
+    fn document() {
+        let value = SerdeValue::Object(indexmap! {
+            "project".into() => SerdeValue::Object(indexmap! {
+                "@version".into() => SerdeValue::Number(dec!(4).into()),
+                "name".into() => SerdeValue::String("Tom & Jerry".into()),
+                "dependency".into() => SerdeValue::List(vec![
+                    SerdeValue::Object(indexmap! {
+                        "@scope".into() => SerdeValue::String("test".into()),
+                        "#text".into() => SerdeValue::String("junit".into()),
+                    }),
+                    SerdeValue::String("serde".into()),
+                ]),
+                "empty".into() => SerdeValue::Null,
+            }),
+        });
+
+        let actual = to_string(&value).unwrap();
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project version="4">
+  <name>Tom &amp; Jerry</name>
+  <dependency scope="test">junit</dependency>
+  <dependency>serde</dependency>
+  <empty/>
+</project>
+"#;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rejects_many_roots() {
+        let value = SerdeValue::Object(indexmap! {
+            "a".into() => SerdeValue::Null,
+            "b".into() => SerdeValue::Null,
+        });
+
+        let actual = to_string(&value).unwrap_err().to_string();
+
+        assert_eq!(
+            actual,
+            "XML cannot represent an object without exactly one root element at the top level"
+        );
+    }
+
+    #[test]
+    fn rejects_nested_lists() {
+        let value = SerdeValue::Object(indexmap! {
+            "a".into() => SerdeValue::Object(indexmap! {
+                "b".into() => SerdeValue::List(vec![SerdeValue::List(vec![])]),
+            }),
+        });
+
+        let actual = to_string(&value).unwrap_err().to_string();
+
+        assert_eq!(actual, "XML cannot represent a nested list at `a.b[0]`");
+    }
+
+    #[test]
+    fn namespaces() {
+        let value = SerdeValue::Object(indexmap! {
+            "svg".into() => SerdeValue::Object(indexmap! {
+                "@xmlns".into() => SerdeValue::String("http://www.w3.org/2000/svg".into()),
+                "@xmlns:xlink".into() => SerdeValue::String("http://www.w3.org/1999/xlink".into()),
+                "@xml:lang".into() => SerdeValue::String("en".into()),
+                "xml-stylesheet".into() => SerdeValue::Null,
+            }),
+        });
+
+        let actual = to_string(&value).unwrap();
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" xml:lang="en">
+  <xml-stylesheet/>
+</svg>
+"#;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rejects_namespace_declaration_elements() {
+        let value = SerdeValue::Object(indexmap! {
+            "xmlns:a".into() => SerdeValue::Null,
+        });
+
+        let actual = to_string(&value).unwrap_err().to_string();
+
+        assert_eq!(
+            actual,
+            r#"XML cannot represent the element name "xmlns:a" at `xmlns:a`"#
+        );
+    }
+}
@@ -13,6 +13,22 @@ pub struct ErrorReport {
 
 impl ErrorReport {
     pub fn display(&self, source: &str, source_id: SourceId) {
+        self.build()
+            .eprint((source_id.clone(), Source::from(source)))
+            .unwrap();
+    }
+
+    /// Display a report whose spans may point into any of the given sources,
+    /// such as earlier entries in a REPL session.
+    pub fn display_sources<I, S>(&self, sources: I)
+    where
+        I: IntoIterator<Item = (SourceId, S)>,
+        S: AsRef<str>,
+    {
+        self.build().eprint(ariadne::sources(sources)).unwrap();
+    }
+
+    fn build(&self) -> Report<'static, Span> {
         let mut report =
             Report::build(ReportKind::Error, self.span.clone()).with_message(self.message.clone());
 
@@ -27,8 +43,6 @@ impl ErrorReport {
         report
             .with_config(Config::default().with_compact(false))
             .finish()
-            .eprint((source_id.clone(), Source::from(source)))
-            .unwrap();
     }
 }
 
@@ -1,6 +1,6 @@
 use chumsky::{extra, input::ValueInput, prelude::*};
 
-use rimu_ast::{Block, SpannedBlock};
+use rimu_ast::{Block, ListItem, ObjectEntry, SpannedBlock};
 use rimu_meta::{Span, Spanned};
 
 use crate::token::{SpannedToken, Token};
@@ -81,7 +81,9 @@ where
 {
     let key = select! {
         Token::String(key) => key,
-        Token::Identifier(key) => key
+        Token::Identifier(key) => key,
+        // though a keyword, `for` may still be a key
+        Token::For => "for".into()
     }
     .map_with(|v, e| Spanned::new(v, e.span()))
     .then_ignore(just(Token::Colon));
@@ -96,7 +98,17 @@ fn object_parser<'src, I>(
 where
     I: ValueInput<'src, Token = Token, Span = Span> + 'src,
 {
-    let entry = entry_parser(block);
+    let computed = expression::expression_parser()
+        .delimited_by(just(Token::LeftBrack), just(Token::RightBrack))
+        .then_ignore(just(Token::Colon))
+        .then(block.clone())
+        .map(|(key, value)| ObjectEntry::Computed(key, value));
+    let entry = entry_parser(block.clone())
+        .map(|(key, value)| ObjectEntry::Entry(key, value))
+        .or(computed)
+        .or(just(Token::Ellipsis)
+            .ignore_then(block)
+            .map(ObjectEntry::Spread));
     let entries = entry.clone().repeated().at_least(1).collect::<Vec<_>>();
     let object = entries
         .clone()
@@ -111,9 +123,13 @@ fn list_parser<'src, I>(
 where
     I: ValueInput<'src, Token = Token, Span = Span> + 'src,
 {
+    let item = just(Token::Ellipsis)
+        .ignore_then(block.clone())
+        .map(ListItem::Spread)
+        .or(block.map(ListItem::Item));
     let list_item = just(Token::Dash)
         .ignore_then(just(Token::Indent))
-        .ignore_then(block)
+        .ignore_then(item)
         .then_ignore(just(Token::Dedent).to(()).or(end()))
         .boxed();
     let list = list_item
@@ -256,7 +272,7 @@ mod tests {
 
     use chumsky::{input::Input, Parser};
     use pretty_assertions::assert_eq;
-    use rimu_ast::{Block, Expression};
+    use rimu_ast::{Block, Expression, ListItem, ObjectEntry};
     use rimu_meta::{SourceId, Span, Spanned};
 
     use crate::Token;
@@ -311,18 +327,18 @@ mod tests {
 
         let expected = Ok(Spanned::new(
             Block::List(vec![
-                Spanned::new(
+                ListItem::Item(Spanned::new(
                     Block::Expression(Expression::Identifier("a".into())),
                     span(2..3),
-                ),
-                Spanned::new(
+                )),
+                ListItem::Item(Spanned::new(
                     Block::Expression(Expression::Identifier("b".into())),
                     span(7..8),
-                ),
-                Spanned::new(
+                )),
+                ListItem::Item(Spanned::new(
                     Block::Expression(Expression::Identifier("c".into())),
                     span(12..13),
-                ),
+                )),
             ]),
             span(0..15),
         ));
@@ -354,21 +370,21 @@ mod tests {
 
         let expected = Ok(Spanned::new(
             Block::Object(vec![
-                (
+                ObjectEntry::Entry(
                     Spanned::new("a".into(), span(0..1)),
                     Spanned::new(
                         Block::Expression(Expression::Identifier("b".into())),
                         span(2..3),
                     ),
                 ),
-                (
+                ObjectEntry::Entry(
                     Spanned::new("c".into(), span(4..5)),
                     Spanned::new(
                         Block::Expression(Expression::Identifier("d".into())),
                         span(6..7),
                     ),
                 ),
-                (
+                ObjectEntry::Entry(
                     Spanned::new("e".into(), span(8..9)),
                     Spanned::new(
                         Block::Expression(Expression::Identifier("f".into())),
@@ -382,6 +398,81 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn computed_key() {
+        //
+        // [a]: b
+        //
+        let actual = test(vec![
+            Token::LeftBrack,
+            Token::Identifier("a".into()),
+            Token::RightBrack,
+            Token::Colon,
+            Token::Identifier("b".into()),
+            Token::EndOfLine,
+        ]);
+
+        let expected = Ok(Spanned::new(
+            Block::Object(vec![ObjectEntry::Computed(
+                Spanned::new(Expression::Identifier("a".into()), span(1..2)),
+                Spanned::new(
+                    Block::Expression(Expression::Identifier("b".into())),
+                    span(4..5),
+                ),
+            )]),
+            span(0..6),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn spread() {
+        //
+        // ...a
+        // b:
+        //   - ...c
+        //
+        let actual = test(vec![
+            Token::Ellipsis,
+            Token::Identifier("a".into()),
+            Token::EndOfLine,
+            Token::Identifier("b".into()),
+            Token::Colon,
+            Token::EndOfLine,
+            Token::Indent,
+            Token::Dash,
+            Token::Indent,
+            Token::Ellipsis,
+            Token::Identifier("c".into()),
+            Token::EndOfLine,
+            Token::Dedent,
+            Token::Dedent,
+        ]);
+
+        let expected = Ok(Spanned::new(
+            Block::Object(vec![
+                ObjectEntry::Spread(Spanned::new(
+                    Block::Expression(Expression::Identifier("a".into())),
+                    span(1..2),
+                )),
+                ObjectEntry::Entry(
+                    Spanned::new("b".into(), span(3..4)),
+                    Spanned::new(
+                        Block::List(vec![ListItem::Spread(Spanned::new(
+                            Block::Expression(Expression::Identifier("c".into())),
+                            span(10..11),
+                        ))]),
+                        span(7..13),
+                    ),
+                ),
+            ]),
+            span(0..14),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn object_hanging_value() {
         //
@@ -411,10 +502,10 @@ mod tests {
 
         let expected = Ok(Spanned::new(
             Block::Object(vec![
-                (
+                ObjectEntry::Entry(
                     Spanned::new("a".into(), span(0..1)),
                     Spanned::new(
-                        Block::Object(vec![(
+                        Block::Object(vec![ObjectEntry::Entry(
                             Spanned::new("b".into(), span(4..5)),
                             Spanned::new(
                                 Block::Expression(Expression::Identifier("c".into())),
@@ -424,7 +515,7 @@ mod tests {
                         span(4..11),
                     ),
                 ),
-                (
+                ObjectEntry::Entry(
                     Spanned::new("d".into(), span(12..13)),
                     Spanned::new(
                         Block::Expression(Expression::Identifier("e".into())),
@@ -483,24 +574,24 @@ mod tests {
         ]);
 
         let expected = Ok(Spanned::new(
-            Block::Object(vec![(
+            Block::Object(vec![ObjectEntry::Entry(
                 Spanned::new("a".into(), span(0..1)),
                 Spanned::new(
                     Block::Object(vec![
-                        (
+                        ObjectEntry::Entry(
                             Spanned::new("b".into(), span(4..5)),
                             Spanned::new(
                                 Block::List(vec![
-                                    Spanned::new(
+                                    ListItem::Item(Spanned::new(
                                         Block::Expression(Expression::Identifier("c".into())),
                                         span(10..11),
-                                    ),
-                                    Spanned::new(
+                                    )),
+                                    ListItem::Item(Spanned::new(
                                         Block::Expression(Expression::Identifier("d".into())),
                                         span(15..16),
-                                    ),
-                                    Spanned::new(
-                                        Block::Object(vec![(
+                                    )),
+                                    ListItem::Item(Spanned::new(
+                                        Block::Object(vec![ObjectEntry::Entry(
                                             Spanned::new("e".into(), span(20..21)),
                                             Spanned::new(
                                                 Block::Expression(Expression::Identifier(
@@ -510,12 +601,12 @@ mod tests {
                                             ),
                                         )]),
                                         span(20..24),
-                                    ),
+                                    )),
                                 ]),
                                 span(8..25),
                             ),
                         ),
-                        (
+                        ObjectEntry::Entry(
                             Spanned::new("g".into(), span(26..27)),
                             Spanned::new(
                                 Block::Expression(Expression::Identifier("h".into())),
@@ -567,16 +658,16 @@ mod tests {
 
         let expected = Ok(Spanned::new(
             Block::List(vec![
-                Spanned::new(
+                ListItem::Item(Spanned::new(
                     Block::Object(vec![
-                        (
+                        ObjectEntry::Entry(
                             Spanned::new("a".into(), span(2..3)),
                             Spanned::new(
                                 Block::Expression(Expression::Identifier("b".into())),
                                 span(4..5),
                             ),
                         ),
-                        (
+                        ObjectEntry::Entry(
                             Spanned::new("c".into(), span(6..7)),
                             Spanned::new(
                                 Block::Expression(Expression::Identifier("d".into())),
@@ -585,17 +676,17 @@ mod tests {
                         ),
                     ]),
                     span(2..10),
-                ),
-                Spanned::new(
+                )),
+                ListItem::Item(Spanned::new(
                     Block::Object(vec![
-                        (
+                        ObjectEntry::Entry(
                             Spanned::new("e".into(), span(13..14)),
                             Spanned::new(
                                 Block::Expression(Expression::Identifier("f".into())),
                                 span(15..16),
                             ),
                         ),
-                        (
+                        ObjectEntry::Entry(
                             Spanned::new("g".into(), span(17..18)),
                             Spanned::new(
                                 Block::Expression(Expression::Identifier("h".into())),
@@ -604,7 +695,7 @@ mod tests {
                         ),
                     ]),
                     span(13..21),
-                ),
+                )),
             ]),
             span(0..22),
         ));
@@ -658,20 +749,20 @@ mod tests {
 
         let expected = Ok(Spanned::new(
             Block::Object(vec![
-                (
+                ObjectEntry::Entry(
                     Spanned::new("a".into(), span(0..1)),
                     Spanned::new(
                         Block::List(vec![
-                            Spanned::new(
+                            ListItem::Item(Spanned::new(
                                 Block::Object(vec![
-                                    (
+                                    ObjectEntry::Entry(
                                         Spanned::new("b".into(), span(6..7)),
                                         Spanned::new(
                                             Block::Expression(Expression::Identifier("c".into())),
                                             span(8..9),
                                         ),
                                     ),
-                                    (
+                                    ObjectEntry::Entry(
                                         Spanned::new("d".into(), span(10..11)),
                                         Spanned::new(
                                             Block::Expression(Expression::Identifier("e".into())),
@@ -680,17 +771,17 @@ mod tests {
                                     ),
                                 ]),
                                 span(6..14),
-                            ),
-                            Spanned::new(
+                            )),
+                            ListItem::Item(Spanned::new(
                                 Block::Object(vec![
-                                    (
+                                    ObjectEntry::Entry(
                                         Spanned::new("f".into(), span(17..18)),
                                         Spanned::new(
                                             Block::Expression(Expression::Identifier("g".into())),
                                             span(19..20),
                                         ),
                                     ),
-                                    (
+                                    ObjectEntry::Entry(
                                         Spanned::new("h".into(), span(21..22)),
                                         Spanned::new(
                                             Block::Expression(Expression::Identifier("i".into())),
@@ -699,12 +790,12 @@ mod tests {
                                     ),
                                 ]),
                                 span(17..25),
-                            ),
+                            )),
                         ]),
                         span(4..26),
                     ),
                 ),
-                (
+                ObjectEntry::Entry(
                     Spanned::new("j".into(), span(27..28)),
                     Spanned::new(
                         Block::Expression(Expression::Identifier("k".into())),
@@ -6,7 +6,10 @@
 // - https://github.com/DennisPrediger/SLAC/blob/main/src/compiler.rs
 
 use chumsky::{extra, input::ValueInput, prelude::*};
-use rimu_ast::{BinaryOperator, Expression, SpannedExpression, UnaryOperator};
+use rimu_ast::{
+    BinaryOperator, ComprehensionClause, Expression, ListItem, ObjectEntry, SpannedExpression,
+    UnaryOperator,
+};
 use rimu_meta::{Span, Spanned};
 
 use crate::{
@@ -76,12 +79,16 @@ where
             .labelled("binary (term) operator");
         let term = binary_operator_parser(factor, op);
 
-        // Next precedence: "comparison" operators: ">", ">=", "<", "<="
+        // Next precedence: "comparison" operators: ">", ">=", "<", "<=", "in"
+        //
+        // `in` can't be confused with the `in` of a `let` block, which always
+        // starts a line after the block's variables.
         let op = just(Token::Less)
             .to(BinaryOperator::Less)
             .or(just(Token::LessEqual).to(BinaryOperator::LessEqual))
             .or(just(Token::Greater).to(BinaryOperator::Greater))
             .or(just(Token::GreaterEqual).to(BinaryOperator::GreaterEqual))
+            .or(just(Token::In).to(BinaryOperator::In))
             .labelled("binary (comparison) operator");
         let comparison = binary_operator_parser(term, op);
 
@@ -110,7 +117,19 @@ where
             .labelled("binary (or) operator");
         let or = binary_operator_parser(and, op);
 
-        or
+        // Next precedence: "??" (null coalesce) operator
+        let op = just(Token::DoubleQuestion)
+            .to(BinaryOperator::NullCoalesce)
+            .labelled("binary (null coalesce) operator");
+        let null_coalesce = binary_operator_parser(or, op);
+
+        // Lowest precedence: "|>" (pipe) operator
+        let op = just(Token::Pipe)
+            .to(BinaryOperator::Pipe)
+            .labelled("binary (pipe) operator");
+        let pipe = binary_operator_parser(null_coalesce, op);
+
+        pipe
     })
     // .then_ignore(just(Token::EndOfLine).to(()).or(end()))
 }
@@ -209,13 +228,28 @@ fn list_parser<'src, I>(
 where
     I: ValueInput<'src, Token = Token, Span = Span> + 'src,
 {
-    let items = items_parser(expr);
-    nested_parser(items, Token::LeftBrack, Token::RightBrack, |_| None)
-        .map(|x| match x {
-            Some(items) => Expression::List(items),
-            None => Expression::Error,
-        })
-        .labelled("list")
+    let comprehension = expr
+        .clone()
+        .then(comprehension_clauses_parser(expr.clone()))
+        .map(|(item, clauses)| Expression::ListComprehension {
+            item: Box::new(item),
+            clauses,
+        });
+    let items = just(Token::Ellipsis)
+        .ignore_then(expr.clone())
+        .map(ListItem::Spread)
+        .or(expr.map(ListItem::Item))
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .collect::<Vec<_>>()
+        .map(Expression::List);
+    nested_parser(
+        comprehension.or(items).boxed(),
+        Token::LeftBrack,
+        Token::RightBrack,
+        |_| Expression::Error,
+    )
+    .labelled("list")
 }
 
 fn object_parser<'src, I>(
@@ -224,34 +258,96 @@ fn object_parser<'src, I>(
 where
     I: ValueInput<'src, Token = Token, Span = Span> + 'src,
 {
-    nested_parser(
-        select! {
-            Token::Identifier(key) => key,
-            Token::String(key) => key
+    let comprehension = expr
+        .clone()
+        .delimited_by(just(Token::LeftParen), just(Token::RightParen))
+        .then_ignore(just(Token::Colon))
+        .then(expr.clone())
+        .then(comprehension_clauses_parser(expr.clone()))
+        .map(|((key, value), clauses)| Expression::ObjectComprehension {
+            key: Box::new(key),
+            value: Box::new(value),
+            clauses,
+        });
+    let entries = select! {
+        Token::Identifier(key) => key,
+        Token::String(key) => key,
+        // though a keyword, `for` may still be a key
+        Token::For => "for".into()
+    }
+    .map_with(|v, e| Spanned::new(v, e.span()))
+    .then(just(Token::Colon).ignore_then(expr.clone().or_not()))
+    .map(|(key, value)| match value {
+        Some(value) => ObjectEntry::Entry(key, value),
+        None => {
+            let (key_string, span) = key.clone().take();
+            let value = Spanned::new(Expression::Identifier(key_string), span);
+            ObjectEntry::Entry(key, value)
         }
-        .map_with(|v, e| Spanned::new(v, e.span()))
-        .then(just(Token::Colon).ignore_then(expr.clone().or_not()))
-        .map(|(key, value)| match value {
-            Some(value) => (key, value),
-            None => {
-                let (key_string, span) = key.clone().take();
-                let value = Spanned::new(Expression::Identifier(key_string), span);
-                (key, value)
-            }
-        })
-        .separated_by(just(Token::Comma))
-        .allow_trailing()
-        .collect::<Vec<_>>()
-        .map(Some)
-        .boxed(),
+    })
+    .or(expr
+        .clone()
+        .delimited_by(just(Token::LeftBrack), just(Token::RightBrack))
+        .then_ignore(just(Token::Colon))
+        .then(expr.clone())
+        .map(|(key, value)| ObjectEntry::Computed(key, value)))
+    .or(just(Token::Ellipsis)
+        .ignore_then(expr.clone())
+        .map(ObjectEntry::Spread))
+    .separated_by(just(Token::Comma))
+    .allow_trailing()
+    .collect::<Vec<_>>()
+    .map(Expression::Object);
+    nested_parser(
+        comprehension.or(entries).boxed(),
         Token::LeftBrace,
         Token::RightBrace,
-        |_| None,
+        |_| Expression::Error,
     )
-    .map(|fields| fields.map(Expression::Object).unwrap_or(Expression::Error))
     .labelled("object")
 }
 
+/// The clauses of a comprehension, starting with a `for` clause.
+fn comprehension_clauses_parser<'src, I>(
+    expr: impl Compiler<'src, I, SpannedExpression> + 'src,
+) -> impl Compiler<'src, I, Vec<Spanned<ComprehensionClause>>> + 'src
+where
+    I: ValueInput<'src, Token = Token, Span = Span> + 'src,
+{
+    let variable = select! { Token::Identifier(name) => name }
+        .map_with(|v, e| Spanned::new(v, e.span()))
+        .labelled("variable");
+    let for_clause = just(Token::For)
+        .ignore_then(
+            variable
+                .separated_by(just(Token::Comma))
+                .at_least(1)
+                .at_most(2)
+                .collect::<Vec<_>>(),
+        )
+        .then_ignore(just(Token::In))
+        .then(expr.clone())
+        .map(|(variables, iterable)| ComprehensionClause::For {
+            variables,
+            iterable,
+        })
+        .map_with(|v, e| Spanned::new(v, e.span()));
+    let if_clause = just(Token::If)
+        .ignore_then(expr)
+        .map(ComprehensionClause::If)
+        .map_with(|v, e| Spanned::new(v, e.span()));
+
+    for_clause
+        .clone()
+        .then(for_clause.or(if_clause).repeated().collect::<Vec<_>>())
+        .map(|(first, mut rest)| {
+            rest.insert(0, first);
+            rest
+        })
+        .labelled("comprehension")
+        .boxed()
+}
+
 pub(crate) fn function_parser<'src, I>(
     expr: impl Compiler<'src, I, SpannedExpression> + 'src,
 ) -> impl Compiler<'src, I, Expression> + 'src
@@ -291,21 +387,32 @@ where
     #[derive(Clone)]
     enum RightUnary {
         Call(Vec<SpannedExpression>),
-        GetIndex(SpannedExpression),
-        GetKey(Spanned<String>),
+        GetIndex(SpannedExpression, bool),
+        GetKey(Spanned<String>, bool),
         GetSlice(Option<SpannedExpression>, Option<SpannedExpression>),
     }
     let call = items
         .clone()
         .delimited_by(just(Token::LeftParen), just(Token::RightParen))
         .map(|expr| RightUnary::Call(expr.unwrap_or_default()));
-    let get_index = expr
-        .clone()
-        .delimited_by(just(Token::LeftBrack), just(Token::RightBrack))
-        .map(RightUnary::GetIndex);
+    let get_index = just(Token::Question)
+        .or_not()
+        .then(
+            expr.clone()
+                .delimited_by(just(Token::LeftBrack), just(Token::RightBrack)),
+        )
+        .map(|(optional, index)| RightUnary::GetIndex(index, optional.is_some()));
     let get_key = just(Token::Dot)
-        .then(select! { Token::Identifier(key) => key }.map_with(|v, e| Spanned::new(v, e.span())))
-        .map(|(_, key)| RightUnary::GetKey(key));
+        .to(false)
+        .or(just(Token::QuestionDot).to(true))
+        .then(
+            select! {
+                Token::Identifier(key) => key,
+                Token::For => "for".into()
+            }
+            .map_with(|v, e| Spanned::new(v, e.span())),
+        )
+        .map(|(optional, key)| RightUnary::GetKey(key, optional));
     let get_slice = expr
         .clone()
         .or_not()
@@ -327,13 +434,15 @@ where
                     function: Box::new(left),
                     args,
                 },
-                RightUnary::GetIndex(index) => Expression::GetIndex {
+                RightUnary::GetIndex(index, optional) => Expression::GetIndex {
                     container: Box::new(left),
                     index: Box::new(index),
+                    optional,
                 },
-                RightUnary::GetKey(key) => Expression::GetKey {
+                RightUnary::GetKey(key, optional) => Expression::GetKey {
                     container: Box::new(left),
                     key,
+                    optional,
                 },
                 RightUnary::GetSlice(start, end) => Expression::GetSlice {
                     container: Box::new(left),
@@ -375,7 +484,10 @@ where
 mod tests {
     use chumsky::{input::Input, Parser};
     use pretty_assertions::assert_eq;
-    use rimu_ast::{BinaryOperator, Expression, SpannedExpression, UnaryOperator};
+    use rimu_ast::{
+        BinaryOperator, ComprehensionClause, Expression, ListItem, ObjectEntry, SpannedExpression,
+        UnaryOperator,
+    };
     use rimu_meta::{SourceId, Span, Spanned};
     use rust_decimal::{prelude::FromPrimitive, Decimal};
     use std::ops::Range;
@@ -455,9 +567,9 @@ mod tests {
 
         let expected = Ok(Spanned::new(
             Expression::List(vec![
-                Spanned::new(Expression::String("hello".into()), span(1..2)),
-                Spanned::new(Expression::Boolean(true), span(3..4)),
-                Spanned::new(Expression::String("world".into()), span(5..6)),
+                ListItem::Item(Spanned::new(Expression::String("hello".into()), span(1..2))),
+                ListItem::Item(Spanned::new(Expression::Boolean(true), span(3..4))),
+                ListItem::Item(Spanned::new(Expression::String("world".into()), span(5..6))),
             ]),
             span(0..8),
         ));
@@ -482,11 +594,11 @@ mod tests {
 
         let expected = Ok(Spanned::new(
             Expression::Object(vec![
-                (
+                ObjectEntry::Entry(
                     Spanned::new("a".into(), span(1..2)),
                     Spanned::new(Expression::String("hello".into()), span(3..4)),
                 ),
-                (
+                ObjectEntry::Entry(
                     Spanned::new("b".into(), span(5..6)),
                     Spanned::new(Expression::String("world".into()), span(7..8)),
                 ),
@@ -497,6 +609,149 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn computed_key() {
+        let actual = test(vec![
+            Token::LeftBrace,
+            Token::LeftBrack,
+            Token::Identifier("a".into()),
+            Token::RightBrack,
+            Token::Colon,
+            Token::Identifier("b".into()),
+            Token::RightBrace,
+        ]);
+
+        let expected = Ok(Spanned::new(
+            Expression::Object(vec![ObjectEntry::Computed(
+                Spanned::new(Expression::Identifier("a".into()), span(2..3)),
+                Spanned::new(Expression::Identifier("b".into()), span(5..6)),
+            )]),
+            span(0..7),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn spread() {
+        let actual = test(vec![
+            Token::LeftBrace,
+            Token::Ellipsis,
+            Token::Identifier("a".into()),
+            Token::Comma,
+            Token::Identifier("b".into()),
+            Token::Colon,
+            Token::LeftBrack,
+            Token::Ellipsis,
+            Token::Identifier("c".into()),
+            Token::Comma,
+            Token::Null,
+            Token::RightBrack,
+            Token::RightBrace,
+        ]);
+
+        let expected = Ok(Spanned::new(
+            Expression::Object(vec![
+                ObjectEntry::Spread(Spanned::new(Expression::Identifier("a".into()), span(2..3))),
+                ObjectEntry::Entry(
+                    Spanned::new("b".into(), span(4..5)),
+                    Spanned::new(
+                        Expression::List(vec![
+                            ListItem::Spread(Spanned::new(
+                                Expression::Identifier("c".into()),
+                                span(8..9),
+                            )),
+                            ListItem::Item(Spanned::new(Expression::Null, span(10..11))),
+                        ]),
+                        span(6..12),
+                    ),
+                ),
+            ]),
+            span(0..13),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn list_comprehension() {
+        let actual = test(vec![
+            Token::LeftBrack,
+            Token::Identifier("x".into()),
+            Token::For,
+            Token::Identifier("x".into()),
+            Token::In,
+            Token::Identifier("xs".into()),
+            Token::If,
+            Token::Identifier("x".into()),
+            Token::RightBrack,
+        ]);
+
+        let expected = Ok(Spanned::new(
+            Expression::ListComprehension {
+                item: Box::new(Spanned::new(Expression::Identifier("x".into()), span(1..2))),
+                clauses: vec![
+                    Spanned::new(
+                        ComprehensionClause::For {
+                            variables: vec![Spanned::new("x".into(), span(3..4))],
+                            iterable: Spanned::new(Expression::Identifier("xs".into()), span(5..6)),
+                        },
+                        span(2..6),
+                    ),
+                    Spanned::new(
+                        ComprehensionClause::If(Spanned::new(
+                            Expression::Identifier("x".into()),
+                            span(7..8),
+                        )),
+                        span(6..8),
+                    ),
+                ],
+            },
+            span(0..9),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn object_comprehension() {
+        let actual = test(vec![
+            Token::LeftBrace,
+            Token::LeftParen,
+            Token::Identifier("k".into()),
+            Token::RightParen,
+            Token::Colon,
+            Token::Identifier("v".into()),
+            Token::For,
+            Token::Identifier("k".into()),
+            Token::Comma,
+            Token::Identifier("v".into()),
+            Token::In,
+            Token::Identifier("o".into()),
+            Token::RightBrace,
+        ]);
+
+        let expected = Ok(Spanned::new(
+            Expression::ObjectComprehension {
+                key: Box::new(Spanned::new(Expression::Identifier("k".into()), span(2..3))),
+                value: Box::new(Spanned::new(Expression::Identifier("v".into()), span(5..6))),
+                clauses: vec![Spanned::new(
+                    ComprehensionClause::For {
+                        variables: vec![
+                            Spanned::new("k".into(), span(7..8)),
+                            Spanned::new("v".into(), span(9..10)),
+                        ],
+                        iterable: Spanned::new(Expression::Identifier("o".into()), span(11..12)),
+                    },
+                    span(6..12),
+                )],
+            },
+            span(0..13),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn expression_group() {
         let actual = test(vec![
@@ -620,6 +875,7 @@ mod tests {
             Expression::GetIndex {
                 container: Box::new(Spanned::new(Expression::Identifier("a".into()), span(0..1))),
                 index: Box::new(Spanned::new(Expression::Number(one), span(2..3))),
+                optional: false,
             },
             span(0..4),
         ));
@@ -640,6 +896,28 @@ mod tests {
             Expression::GetKey {
                 container: Box::new(Spanned::new(Expression::Identifier("a".into()), span(0..1))),
                 key: Spanned::new("b".into(), span(2..3)),
+                optional: false,
+            },
+            span(0..3),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn optional_get_key() {
+        let tokens = vec![
+            Token::Identifier("a".into()),
+            Token::QuestionDot,
+            Token::Identifier("b".into()),
+        ];
+        let actual = test(tokens);
+
+        let expected = Ok(Spanned::new(
+            Expression::GetKey {
+                container: Box::new(Spanned::new(Expression::Identifier("a".into()), span(0..1))),
+                key: Spanned::new("b".into(), span(2..3)),
+                optional: true,
             },
             span(0..3),
         ));
@@ -647,6 +925,164 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn optional_get_index() {
+        let tokens = vec![
+            Token::Identifier("a".into()),
+            Token::Question,
+            Token::LeftBrack,
+            Token::String("b".into()),
+            Token::RightBrack,
+        ];
+        let actual = test(tokens);
+
+        let expected = Ok(Spanned::new(
+            Expression::GetIndex {
+                container: Box::new(Spanned::new(Expression::Identifier("a".into()), span(0..1))),
+                index: Box::new(Spanned::new(Expression::String("b".into()), span(3..4))),
+                optional: true,
+            },
+            span(0..5),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn precedence_pipe() {
+        let tokens = vec![
+            Token::Identifier("a".into()),
+            Token::DoubleQuestion,
+            Token::Identifier("b".into()),
+            Token::Pipe,
+            Token::Identifier("c".into()),
+            Token::LeftParen,
+            Token::Identifier("d".into()),
+            Token::RightParen,
+            Token::Pipe,
+            Token::Identifier("e".into()),
+        ];
+        let actual = test(tokens);
+
+        let expected = Ok(Spanned::new(
+            Expression::Binary {
+                left: Box::new(Spanned::new(
+                    Expression::Binary {
+                        left: Box::new(Spanned::new(
+                            Expression::Binary {
+                                left: Box::new(Spanned::new(
+                                    Expression::Identifier("a".into()),
+                                    span(0..1),
+                                )),
+                                operator: BinaryOperator::NullCoalesce,
+                                right: Box::new(Spanned::new(
+                                    Expression::Identifier("b".into()),
+                                    span(2..3),
+                                )),
+                            },
+                            span(0..3),
+                        )),
+                        operator: BinaryOperator::Pipe,
+                        right: Box::new(Spanned::new(
+                            Expression::Call {
+                                function: Box::new(Spanned::new(
+                                    Expression::Identifier("c".into()),
+                                    span(4..5),
+                                )),
+                                args: vec![Spanned::new(
+                                    Expression::Identifier("d".into()),
+                                    span(6..7),
+                                )],
+                            },
+                            span(4..8),
+                        )),
+                    },
+                    span(0..8),
+                )),
+                operator: BinaryOperator::Pipe,
+                right: Box::new(Spanned::new(
+                    Expression::Identifier("e".into()),
+                    span(9..10),
+                )),
+            },
+            span(0..10),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn precedence_in_equal() {
+        let tokens = vec![
+            Token::Identifier("a".into()),
+            Token::In,
+            Token::Identifier("b".into()),
+            Token::Equal,
+            Token::Boolean(true),
+        ];
+        let actual = test(tokens);
+
+        let expected = Ok(Spanned::new(
+            Expression::Binary {
+                left: Box::new(Spanned::new(
+                    Expression::Binary {
+                        left: Box::new(Spanned::new(
+                            Expression::Identifier("a".into()),
+                            span(0..1),
+                        )),
+                        operator: BinaryOperator::In,
+                        right: Box::new(Spanned::new(
+                            Expression::Identifier("b".into()),
+                            span(2..3),
+                        )),
+                    },
+                    span(0..3),
+                )),
+                operator: BinaryOperator::Equal,
+                right: Box::new(Spanned::new(Expression::Boolean(true), span(4..5))),
+            },
+            span(0..5),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn precedence_null_coalesce_or() {
+        let tokens = vec![
+            Token::Identifier("a".into()),
+            Token::Or,
+            Token::Identifier("b".into()),
+            Token::DoubleQuestion,
+            Token::Identifier("c".into()),
+        ];
+        let actual = test(tokens);
+
+        let expected = Ok(Spanned::new(
+            Expression::Binary {
+                left: Box::new(Spanned::new(
+                    Expression::Binary {
+                        left: Box::new(Spanned::new(
+                            Expression::Identifier("a".into()),
+                            span(0..1),
+                        )),
+                        operator: BinaryOperator::Or,
+                        right: Box::new(Spanned::new(
+                            Expression::Identifier("b".into()),
+                            span(2..3),
+                        )),
+                    },
+                    span(0..3),
+                )),
+                operator: BinaryOperator::NullCoalesce,
+                right: Box::new(Spanned::new(Expression::Identifier("c".into()), span(4..5))),
+            },
+            span(0..5),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn get_slice() {
         let one = Decimal::from_u8(1).unwrap();
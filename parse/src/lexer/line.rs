@@ -11,7 +11,6 @@ use chumsky::{
 };
 use rimu_meta::{SourceId, Span, Spanned};
 use rust_decimal::Decimal;
-use std::str::FromStr;
 
 use crate::token::{SpannedToken, Token};
 
@@ -75,46 +74,68 @@ where
     I: ValueInput<'src, Token = char, Span = Span> + 'src,
 {
     let digit = any::<I, _>().filter(|c: &char| c.is_ascii_digit());
-    let octal_digit = any::<I, _>().filter(|c: &char| matches!(c, '0'..='7'));
 
     // Integer part: a single `0`, or a non-zero digit followed by any digits.
-    // Leading zeros are rejected so `0o..`, `0x..`, etc. remain available as future number prefixes.
+    // Leading zeros are rejected so `0o..`, `0x..`, etc. remain available as number prefixes.
     let int = choice((
         just('0').map(|c: char| c.to_string()),
-        any::<I, _>()
-            .filter(|c: &char| matches!(c, '1'..='9'))
-            .then(digit.repeated().collect::<String>())
-            .map(|(first, rest): (char, String)| {
-                let mut s = String::with_capacity(1 + rest.len());
-                s.push(first);
-                s.push_str(&rest);
-                s
-            }),
+        digits(
+            any::<I, _>().filter(|c: &char| matches!(c, '1'..='9')),
+            digit,
+        ),
     ));
 
-    let frac = just('.').ignore_then(digit.repeated().at_least(1).collect::<String>());
+    let frac = just('.').ignore_then(digits(digit, digit));
 
-    let octal = just("0o")
-        .ignore_then(octal_digit.repeated().at_least(1).collect::<String>())
-        .try_map(|s: String, span| {
-            u64::from_str_radix(&s, 8)
-                .map(Decimal::from)
-                .map_err(|e| Rich::custom(span, format!("{}", e)))
+    let exponent = one_of("eE")
+        .ignore_then(one_of("+-").or_not())
+        .then(digits(digit, digit))
+        .map(|(sign, digits): (Option<char>, String)| match sign {
+            Some('-') => format!("-{}", digits),
+            _ => digits,
         });
 
-    let decimal = int
-        .then(frac.or_not())
-        .map(
-            |(int_part, frac_part): (String, Option<String>)| match frac_part {
+    // Integers with a radix prefix, such as `0x`, `0o` and `0b`.
+    let radix_int = |prefix: &'static str, radix: u32| {
+        let radix_digit = any::<I, _>().filter(move |c: &char| c.is_digit(radix));
+        just(prefix)
+            .ignore_then(digits(radix_digit, radix_digit))
+            .map(move |s: String| {
+                i128::from_str_radix(&s, radix)
+                    .ok()
+                    .and_then(|n| Decimal::try_from_i128_with_scale(n, 0).ok())
+            })
+    };
+
+    let decimal = int.then(frac.or_not()).then(exponent.or_not()).map(
+        |((int_part, frac_part), exponent): ((String, Option<String>), Option<String>)| {
+            let s = match frac_part {
                 Some(frac) => format!("{}.{}", int_part, frac),
                 None => int_part,
-            },
-        )
-        .try_map(|s, span| Decimal::from_str(&s).map_err(|e| Rich::custom(span, format!("{}", e))));
-
-    let number = choice((octal, decimal))
-        .map(Token::Number)
-        .labelled("number");
+            };
+            match exponent {
+                Some(exponent) => scientific(&s, &exponent),
+                None => exact(&s),
+            }
+        },
+    );
+
+    let number = choice((
+        radix_int("0x", 16),
+        radix_int("0o", 8),
+        radix_int("0b", 2),
+        decimal,
+    ))
+    // Checked once a number is matched, so an out of range number is reported
+    // as such rather than lexed as something else.
+    .validate(|number, e, emitter| {
+        number.unwrap_or_else(|| {
+            emitter.emit(Rich::custom(e.span(), "number is out of range"));
+            Decimal::ZERO
+        })
+    })
+    .map(Token::Number)
+    .labelled("number");
 
     let escape = just('\\')
         .ignore_then(choice((
@@ -155,7 +176,9 @@ where
     let control = choice((
         just(',').to(Token::Comma),
         just(':').to(Token::Colon),
+        just("...").to(Token::Ellipsis),
         just('.').to(Token::Dot),
+        just("?.").to(Token::QuestionDot),
         just("=>").to(Token::FatArrow),
     ))
     .labelled("control");
@@ -167,6 +190,8 @@ where
         just("!=").to(Token::NotEqual),
         just("&&").to(Token::And),
         just("||").to(Token::Or),
+        just("??").to(Token::DoubleQuestion),
+        just("|>").to(Token::Pipe),
         just('+').to(Token::Plus),
         just('-').to(Token::Dash),
         just('*').to(Token::Star),
@@ -176,6 +201,7 @@ where
         just('^').to(Token::Xor),
         just('!').to(Token::Not),
         just('%').to(Token::Rem),
+        just('?').to(Token::Question),
     ))
     .labelled("operator");
 
@@ -189,6 +215,7 @@ where
             "else" => Token::Else,
             "let" => Token::Let,
             "in" => Token::In,
+            "for" => Token::For,
             _ => Token::Identifier(ident),
         })
         .labelled("identifier");
@@ -217,6 +244,64 @@ where
         .then_ignore(end())
 }
 
+/// A first digit, then any more digits, optionally grouped by single `_`
+/// separators between them, as in `1_000_000`.
+fn digits<'src, I>(
+    first: impl Parser<'src, I, char, extra::Err<Rich<'src, char, Span>>> + Clone,
+    digit: impl Parser<'src, I, char, extra::Err<Rich<'src, char, Span>>> + Clone,
+) -> impl Parser<'src, I, String, extra::Err<Rich<'src, char, Span>>> + Clone
+where
+    I: ValueInput<'src, Token = char, Span = Span>,
+{
+    let rest = just('_')
+        .or_not()
+        .ignore_then(digit)
+        .repeated()
+        .collect::<String>();
+    first.then(rest).map(|(f, r): (char, String)| {
+        let mut s = String::with_capacity(1 + r.len());
+        s.push(f);
+        s.push_str(&r);
+        s
+    })
+}
+
+/// The exact decimal for a string of digits, or `None` if it can't be
+/// represented without rounding. Trailing zeros of the fraction only add
+/// scale, so they're dropped when there are too many to fit.
+fn exact(decimal: &str) -> Option<Decimal> {
+    match Decimal::from_str_exact(decimal) {
+        Ok(decimal) => Some(decimal),
+        Err(_) if decimal.contains('.') && decimal.ends_with('0') => {
+            exact(decimal[..decimal.len() - 1].trim_end_matches('.'))
+        }
+        Err(_) => None,
+    }
+}
+
+/// The exact decimal `significand * 10^exponent`, or `None` if it's out of range.
+fn scientific(significand: &str, exponent: &str) -> Option<Decimal> {
+    let significand = exact(significand)?;
+    let mut mantissa = significand.mantissa();
+    if mantissa == 0 {
+        return Some(Decimal::ZERO);
+    }
+    let mut exponent = exponent
+        .parse::<i64>()
+        .ok()?
+        .checked_sub(significand.scale() as i64)?;
+    while exponent < 0 && mantissa % 10 == 0 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+    while exponent > 0 {
+        mantissa = mantissa.checked_mul(10)?;
+        exponent -= 1;
+    }
+    let scale = u32::try_from(-exponent).ok()?;
+    Decimal::try_from_i128_with_scale(mantissa, scale).ok()
+}
+
 fn ident<'src, I>() -> impl Parser<'src, I, String, extra::Err<Rich<'src, char, Span>>> + Clone
 where
     I: ValueInput<'src, Token = char, Span = Span>,
@@ -240,7 +325,7 @@ mod tests {
     use pretty_assertions::assert_eq;
     use rimu_meta::{SourceId, Span, Spanned};
     use rust_decimal::{prelude::FromPrimitive, Decimal};
-    use std::{f64::consts::PI, ops::Range};
+    use std::{f64::consts::PI, ops::Range, str::FromStr};
 
     use crate::token::{SpannedToken, Token};
 
@@ -378,6 +463,54 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn optional_chaining() {
+        let actual = test("a?.b?[0] ?? c");
+
+        let expected = Ok(vec![
+            Spanned::new(Token::Identifier(String::from("a")), span(0..1)),
+            Spanned::new(Token::QuestionDot, span(1..3)),
+            Spanned::new(Token::Identifier(String::from("b")), span(3..4)),
+            Spanned::new(Token::Question, span(4..5)),
+            Spanned::new(Token::LeftBrack, span(5..6)),
+            Spanned::new(Token::Number(Decimal::from_u8(0).unwrap()), span(6..7)),
+            Spanned::new(Token::RightBrack, span(7..8)),
+            Spanned::new(Token::DoubleQuestion, span(9..11)),
+            Spanned::new(Token::Identifier(String::from("c")), span(12..13)),
+        ]);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn pipe() {
+        let actual = test("a |> b");
+
+        let expected = Ok(vec![
+            Spanned::new(Token::Identifier(String::from("a")), span(0..1)),
+            Spanned::new(Token::Pipe, span(2..4)),
+            Spanned::new(Token::Identifier(String::from("b")), span(5..6)),
+        ]);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn spread() {
+        let actual = test("[...a, b]");
+
+        let expected = Ok(vec![
+            Spanned::new(Token::LeftBrack, span(0..1)),
+            Spanned::new(Token::Ellipsis, span(1..4)),
+            Spanned::new(Token::Identifier(String::from("a")), span(4..5)),
+            Spanned::new(Token::Comma, span(5..6)),
+            Spanned::new(Token::Identifier(String::from("b")), span(7..8)),
+            Spanned::new(Token::RightBrack, span(8..9)),
+        ]);
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn unterminated_less() {
         let actual = test("<");
@@ -471,6 +604,86 @@ mod tests {
         assert_ne!(actual, ok_as_zero_over_full_span);
     }
 
+    fn test_exact_number(input: &str, expected: &str) {
+        let actual = test(input);
+
+        let expected = Ok(vec![Spanned::new(
+            Token::Number(Decimal::from_str(expected).unwrap()),
+            span(0..input.chars().count()),
+        )]);
+
+        assert_eq!(actual, expected, "{}", input);
+    }
+
+    #[test]
+    fn hex_and_binary() {
+        test_exact_number("0xff", "255");
+        test_exact_number("0xFF_FF", "65535");
+        test_exact_number("0b1010", "10");
+        test_exact_number("0b1111_0000", "240");
+        test_exact_number("0o7_55", "493");
+    }
+
+    #[test]
+    fn digit_separators() {
+        test_exact_number("1_000_000", "1000000");
+        test_exact_number("3.141_592", "3.141592");
+        test_exact_number("1_0e1_0", "100000000000");
+    }
+
+    #[test]
+    fn trailing_zeros() {
+        test_exact_number("1.000000000000000000000000000000", "1");
+        test_exact_number("1.000000000000000000000000000000e2", "100");
+    }
+
+    #[test]
+    fn exponents() {
+        test_exact_number("1.5e3", "1500");
+        test_exact_number("1E6", "1000000");
+        test_exact_number("2.5e+2", "250");
+        test_exact_number("15e-1", "1.5");
+        test_exact_number("1e-28", "0.0000000000000000000000000001");
+        test_exact_number("1000e-30", "0.000000000000000000000000001");
+        test_exact_number("0e999", "0");
+        test_exact_number(
+            "1.0000000000000000000000000001e0",
+            "1.0000000000000000000000000001",
+        );
+    }
+
+    #[test]
+    fn err_number_out_of_range() {
+        for input in [
+            "1e29",
+            "1e-29",
+            "79228162514264337593543950336",
+            "0x1_0000_0000_0000_0000_0000_0000",
+            // too precise, rather than rounded
+            "0.12345678901234567890123456789012345",
+            "1.00000000000000000000000000001e0",
+            "1.00000000000000000000000000001",
+        ] {
+            assert!(test(input).is_err(), "{}", input);
+        }
+        // the largest representable integer
+        test_exact_number(
+            "0xffff_ffff_ffff_ffff_ffff_ffff",
+            "79228162514264337593543950335",
+        );
+    }
+
+    #[test]
+    fn err_hex_no_digits() {
+        let actual = test("0x");
+
+        let ok_as_zero_over_full_span = Ok(vec![Spanned::new(
+            Token::Number(Decimal::from_u32(0).unwrap()),
+            span(0..2),
+        )]);
+        assert_ne!(actual, ok_as_zero_over_full_span);
+    }
+
     #[test]
     fn err_unknown_token_1() {
         let actual = test("^&");
@@ -90,6 +90,15 @@ impl<'src> LinesLexer<'src> {
                 continue;
             }
 
+            // A line starting with a pipe (`|>`) continues the previous line,
+            // without affecting indentation tracking.
+            if self.is_continuation(&space, &rest, &tokens) {
+                tokens.pop();
+                tokens.push(self.line(rest));
+                tokens.push(Spanned::new(LinesToken::EndOfLine, ending_span));
+                continue;
+            }
+
             let dents = self.get_dents(space.clone())?;
             tokens.extend(dents);
 
@@ -108,6 +117,20 @@ impl<'src> LinesLexer<'src> {
         Ok(tokens)
     }
 
+    fn is_continuation(
+        &self,
+        space: &Spanned<&'src str>,
+        rest: &Spanned<&'src str>,
+        tokens: &[SpannedLinesToken<'src>],
+    ) -> bool {
+        rest.inner().starts_with("|>")
+            && space.inner().len() >= self.indentation()
+            && matches!(
+                tokens.last().map(|token| token.inner()),
+                Some(LinesToken::EndOfLine)
+            )
+    }
+
     fn get_space_index(&self, line: &'src str) -> Option<usize> {
         line.char_indices()
             .skip_while(|&(_, c)| c == ' ' || c == '\t')
@@ -406,6 +429,29 @@ a:
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn pipe_continuation() {
+        let actual = test(
+            "
+a: b
+  |> c
+  |> d(e)
+f: g
+",
+        );
+
+        let expected = Ok(vec![
+            Spanned::new(LinesToken::Line("a: b"), span(1..5)),
+            Spanned::new(LinesToken::Line("|> c"), span(8..12)),
+            Spanned::new(LinesToken::Line("|> d(e)"), span(15..22)),
+            Spanned::new(LinesToken::EndOfLine, span(22..23)),
+            Spanned::new(LinesToken::Line("f: g"), span(23..27)),
+            Spanned::new(LinesToken::EndOfLine, span(27..28)),
+        ]);
+
+        assert_eq!(actual, expected);
+    }
+
     // TODO tests
     // - list mania: lists within lists within lists
     // - list marker vs negate unary
@@ -9,7 +9,7 @@ mod token;
 pub use crate::error::Error;
 pub(crate) use compiler::{compile_block, compile_expression};
 pub(crate) use lexer::{tokenize_block, tokenize_expression};
-pub(crate) use token::{SpannedToken, Token};
+pub use token::{SpannedToken, Token};
 
 pub fn parse_expression(code: &str, source: SourceId) -> (Option<SpannedExpression>, Vec<Error>) {
     let mut errors = Vec::new();
@@ -30,6 +30,14 @@ pub fn parse_expression(code: &str, source: SourceId) -> (Option<SpannedExpressi
     (output, errors)
 }
 
+/// Split a line of code into tokens, such as for syntax highlighting.
+///
+/// Token spans count chars, not bytes.
+pub fn tokenize(code: &str, source: SourceId) -> (Option<Vec<SpannedToken>>, Vec<Error>) {
+    let (tokens, errors) = tokenize_expression(code, source);
+    (tokens, errors.into_iter().map(Error::Lexer).collect())
+}
+
 pub fn parse_block(code: &str, source: SourceId) -> (Option<SpannedBlock>, Vec<Error>) {
     let mut errors = Vec::new();
 
@@ -54,7 +62,9 @@ mod tests {
     use std::ops::Range;
 
     use pretty_assertions::assert_eq;
-    use rimu_ast::{BinaryOperator, Block, Expression, SpannedBlock, SpannedExpression};
+    use rimu_ast::{
+        BinaryOperator, Block, Expression, ListItem, ObjectEntry, SpannedBlock, SpannedExpression,
+    };
     use rimu_meta::{SourceId, Span, Spanned};
 
     use crate::{parse_block, parse_expression, Error};
@@ -124,15 +134,15 @@ a:
         );
 
         let expected_block = Some(Spanned::new(
-            Block::Object(vec![(
+            Block::Object(vec![ObjectEntry::Entry(
                 Spanned::new("a".into(), span(1..2)),
                 Spanned::new(
                     Block::Object(vec![
-                        (
+                        ObjectEntry::Entry(
                             Spanned::new("b".into(), span(6..7)),
                             Spanned::new(
                                 Block::List(vec![
-                                    Spanned::new(
+                                    ListItem::Item(Spanned::new(
                                         Block::Expression(Expression::Binary {
                                             left: Box::new(Spanned::new(
                                                 Expression::Identifier("c".into()),
@@ -145,9 +155,9 @@ a:
                                             operator: BinaryOperator::Add,
                                         }),
                                         span(15..20),
-                                    ),
-                                    Spanned::new(
-                                        Block::Object(vec![(
+                                    )),
+                                    ListItem::Item(Spanned::new(
+                                        Block::Object(vec![ObjectEntry::Entry(
                                             Spanned::new("e".into(), span(27..28)),
                                             Spanned::new(
                                                 Block::Expression(Expression::Identifier(
@@ -157,12 +167,12 @@ a:
                                             ),
                                         )]),
                                         span(27..32),
-                                    ),
+                                    )),
                                 ]),
                                 span(13..34),
                             ),
                         ),
-                        (
+                        ObjectEntry::Entry(
                             Spanned::new("g".into(), span(34..35)),
                             Spanned::new(
                                 Block::Expression(Expression::Identifier("h".into())),
@@ -192,10 +202,10 @@ a:
         );
 
         let expected_block = Some(Spanned::new(
-            Block::Object(vec![(
+            Block::Object(vec![ObjectEntry::Entry(
                 Spanned::new("a".into(), span(19..20)),
                 Spanned::new(
-                    Block::Object(vec![(
+                    Block::Object(vec![ObjectEntry::Entry(
                         Spanned::new("b".into(), span(43..44)),
                         Spanned::new(
                             Block::Expression(Expression::Identifier("c".into())),
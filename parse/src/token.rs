@@ -46,6 +46,8 @@ pub enum Token {
     Let,
     /// in
     In,
+    /// for
+    For,
 
     /// ,
     Comma,
@@ -54,6 +56,10 @@ pub enum Token {
 
     /// .
     Dot,
+    /// ?.
+    QuestionDot,
+    /// ...
+    Ellipsis,
 
     /// =>
     FatArrow,
@@ -88,6 +94,12 @@ pub enum Token {
     Not,
     /// %
     Rem,
+    /// ?
+    Question,
+    /// ??
+    DoubleQuestion,
+    /// |>
+    Pipe,
 }
 
 impl fmt::Display for Token {
@@ -116,9 +128,12 @@ impl fmt::Display for Token {
             Token::Else => write!(f, "else"),
             Token::Let => write!(f, "let"),
             Token::In => write!(f, "in"),
+            Token::For => write!(f, "for"),
             Token::Comma => write!(f, ","),
             Token::Colon => write!(f, ":"),
             Token::Dot => write!(f, "."),
+            Token::QuestionDot => write!(f, "?."),
+            Token::Ellipsis => write!(f, "..."),
             Token::FatArrow => write!(f, "=>"),
             Token::Plus => write!(f, "+"),
             Token::Dash => write!(f, "-"),
@@ -135,6 +150,9 @@ impl fmt::Display for Token {
             Token::Xor => write!(f, "^"),
             Token::Not => write!(f, "!"),
             Token::Rem => write!(f, "%"),
+            Token::Question => write!(f, "?"),
+            Token::DoubleQuestion => write!(f, "??"),
+            Token::Pipe => write!(f, "|>"),
         }
     }
 }
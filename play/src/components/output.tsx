@@ -3,7 +3,7 @@
 import { useCallback, ChangeEventHandler } from 'react'
 import { Code, Flex, NativeSelect, HStack } from '@chakra-ui/react'
 
-export type Format = 'json' | 'yaml' | 'toml'
+export type Format = 'json' | 'yaml' | 'toml' | 'hcl' | 'ini' | 'dotenv' | 'xml' | 'nix'
 
 export type OutputData = string
 
@@ -63,6 +63,11 @@ export function FormatSelect(props: FormatSelectProps) {
           <option value="json">JSON</option>
           <option value="yaml">YAML</option>
           <option value="toml">TOML</option>
+          <option value="hcl">HCL</option>
+          <option value="ini">INI</option>
+          <option value="dotenv">dotenv</option>
+          <option value="xml">XML</option>
+          <option value="nix">Nix</option>
         </NativeSelect.Field>
         <NativeSelect.Indicator />
       </NativeSelect.Root>
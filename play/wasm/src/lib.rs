@@ -22,6 +22,11 @@ pub enum Format {
     Json = "json",
     Yaml = "yaml",
     Toml = "toml",
+    Hcl = "hcl",
+    Ini = "ini",
+    Dotenv = "dotenv",
+    Xml = "xml",
+    Nix = "nix",
 }
 
 #[wasm_bindgen]
@@ -50,14 +55,19 @@ pub fn render(code: &str, source_id: &str, format: Format) -> Result<String, JsV
     let value: Value = value.into_inner();
     let value: SerdeValue = value.into();
 
-    let output: Result<String, OutputFormatError> = match format {
-        Format::Json => serde_json::to_string_pretty(&value).map_err(OutputFormatError::new),
-        Format::Yaml => serde_yaml::to_string(&value).map_err(OutputFormatError::new),
-        Format::Toml => toml::to_string_pretty(&value).map_err(OutputFormatError::new),
+    let format = match format {
+        Format::Json => rimu_format::Format::Json,
+        Format::Yaml => rimu_format::Format::Yaml,
+        Format::Toml => rimu_format::Format::Toml,
+        Format::Hcl => rimu_format::Format::Hcl,
+        Format::Ini => rimu_format::Format::Ini,
+        Format::Dotenv => rimu_format::Format::Dotenv,
+        Format::Xml => rimu_format::Format::Xml,
+        Format::Nix => rimu_format::Format::Nix,
         _ => panic!("Unexpected format!"),
     };
 
-    match output {
+    match rimu_format::to_string_pretty(&value, format).map_err(OutputFormatError::new) {
         Ok(output) => Ok(output),
         Err(error) => Err(to_js_value(&error)?),
     }
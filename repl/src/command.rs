@@ -0,0 +1,139 @@
+use rimu_format::Format;
+
+/// A command entered after a `:`, rather than code to evaluate.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Command<'a> {
+    Env,
+    Unset(Vec<&'a str>),
+    Load(&'a str),
+    Reload,
+    /// How to print results, or `None` to use [`rimu::Value`]'s `Display`.
+    Format(Option<Format>),
+    Type(&'a str),
+    Ast(&'a str),
+    Tokens(&'a str),
+    Time(&'a str),
+    Help,
+}
+
+impl<'a> Command<'a> {
+    /// Parse a command, without its leading `:`. An error is the message to
+    /// show instead, such as the usage of the command.
+    pub(crate) fn parse(command: &'a str) -> Result<Self, String> {
+        let command = command.trim();
+        let name = command.split_whitespace().next().unwrap_or_default();
+        let rest = command[name.len()..].trim_start();
+        let usage = |usage: &str| Err(format!("Usage: :{} {}", name, usage));
+        match name {
+            "env" => Ok(Command::Env),
+            "unset" => Ok(Command::Unset(rest.split_whitespace().collect())),
+            "load" if rest.is_empty() => usage("PATH"),
+            "load" => Ok(Command::Load(rest)),
+            "reload" => Ok(Command::Reload),
+            "format" => match rest {
+                "" => usage("display|yaml|json|toml|hcl|ini|dotenv|xml|nix"),
+                "display" => Ok(Command::Format(None)),
+                format => format
+                    .parse()
+                    .map(|format| Command::Format(Some(format)))
+                    .map_err(|error| error.to_string()),
+            },
+            "type" | "ast" | "tokens" | "time" if rest.is_empty() => usage("EXPR"),
+            "type" => Ok(Command::Type(rest)),
+            "ast" => Ok(Command::Ast(rest)),
+            "tokens" => Ok(Command::Tokens(rest)),
+            "time" => Ok(Command::Time(rest)),
+            "help" => Ok(Command::Help),
+            _ => Err(format!("Unknown command: :{} (try :help)", command)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use rimu_format::Format;
+
+    use super::Command;
+
+    #[test]
+    fn load_and_reload() {
+        assert_eq!(
+            Command::parse("load lib.rimu"),
+            Ok(Command::Load("lib.rimu"))
+        );
+        assert_eq!(
+            Command::parse("load  my lib.rimu "),
+            Ok(Command::Load("my lib.rimu"))
+        );
+        assert_eq!(Command::parse("load"), Err("Usage: :load PATH".into()));
+        assert_eq!(Command::parse("reload"), Ok(Command::Reload));
+    }
+
+    #[test]
+    fn bindings() {
+        assert_eq!(Command::parse("env"), Ok(Command::Env));
+        assert_eq!(
+            Command::parse("unset a  b"),
+            Ok(Command::Unset(vec!["a", "b"]))
+        );
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(Command::parse("format display"), Ok(Command::Format(None)));
+        assert_eq!(
+            Command::parse("format yaml"),
+            Ok(Command::Format(Some(Format::Yaml)))
+        );
+        assert_eq!(
+            Command::parse("format json"),
+            Ok(Command::Format(Some(Format::Json)))
+        );
+        assert_eq!(
+            Command::parse("format nix"),
+            Ok(Command::Format(Some(Format::Nix)))
+        );
+        assert_eq!(
+            Command::parse("format yeml"),
+            Err("Unknown format: yeml".into())
+        );
+        assert_eq!(
+            Command::parse("format"),
+            Err("Usage: :format display|yaml|json|toml|hcl|ini|dotenv|xml|nix".into())
+        );
+    }
+
+    #[test]
+    fn introspection() {
+        assert_eq!(
+            Command::parse("type { a: 1 }"),
+            Ok(Command::Type("{ a: 1 }"))
+        );
+        assert_eq!(Command::parse("ast  a + b"), Ok(Command::Ast("a + b")));
+        assert_eq!(
+            Command::parse("tokens \"a b\""),
+            Ok(Command::Tokens("\"a b\""))
+        );
+        assert_eq!(
+            Command::parse("time sum([1, 2])"),
+            Ok(Command::Time("sum([1, 2])"))
+        );
+        for name in ["type", "ast", "tokens", "time"] {
+            assert_eq!(Command::parse(name), Err(format!("Usage: :{} EXPR", name)));
+        }
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!(
+            Command::parse("nope 1"),
+            Err("Unknown command: :nope 1 (try :help)".into())
+        );
+        assert_eq!(
+            Command::parse(""),
+            Err("Unknown command: : (try :help)".into())
+        );
+    }
+}
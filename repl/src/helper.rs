@@ -0,0 +1,194 @@
+use std::{borrow::Cow, cell::RefCell, collections::BTreeSet, rc::Rc};
+
+use rimu::{tokenize, Environment, SourceId, Token, Value};
+use rustyline::{
+    completion::{Completer, Pair},
+    highlight::{CmdKind, Highlighter},
+    Context, Helper, Hinter, Validator,
+};
+
+use crate::statement::KEYWORDS;
+
+const RESET: &str = "\x1b[0m";
+const KEYWORD: &str = "\x1b[35m";
+const LITERAL: &str = "\x1b[33m";
+const STRING: &str = "\x1b[32m";
+const INVALID: &str = "\x1b[31m";
+
+/// Completes and highlights REPL input, using the session environment.
+#[derive(Helper, Hinter, Validator)]
+pub(crate) struct ReplHelper {
+    env: Rc<RefCell<Environment>>,
+}
+
+impl ReplHelper {
+    pub(crate) fn new(env: Rc<RefCell<Environment>>) -> Self {
+        Self { env }
+    }
+
+    /// Complete the word before the cursor: after a `.`, with the keys of the
+    /// object before it, otherwise with the variables in scope. Returns where
+    /// the word starts, and the names it could be.
+    fn completions(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let before = &line[..pos];
+        let start = before
+            .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+            .map(|index| index + 1)
+            .unwrap_or(0);
+        let word = &before[start..];
+
+        let names: BTreeSet<String> = match before[..start].strip_suffix('.') {
+            Some(object_path) => {
+                let path_start = object_path
+                    .rfind(|c: char| {
+                        !(c.is_ascii_alphanumeric() || c == '_' || c == '$' || c == '.')
+                    })
+                    .map(|index| index + 1)
+                    .unwrap_or(0);
+                let keys: Vec<&str> = object_path[path_start..].split('.').collect();
+                match self
+                    .env
+                    .borrow()
+                    .get_in(keys)
+                    .map(|value| value.into_inner())
+                {
+                    Some(Value::Object(object)) => object.keys().cloned().collect(),
+                    _ => BTreeSet::new(),
+                }
+            }
+            None => self
+                .env
+                .borrow()
+                .iter()
+                .map(|(name, _)| name)
+                .chain(KEYWORDS.iter().map(ToString::to_string))
+                .collect(),
+        };
+
+        let names = names
+            .into_iter()
+            .filter(|name| name.starts_with(word))
+            .collect();
+        (start, names)
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, names) = self.completions(line, pos);
+        let candidates = names
+            .into_iter()
+            .map(|name| Pair {
+                display: name.clone(),
+                replacement: name,
+            })
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+impl Highlighter for ReplHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        let (Some(tokens), _) = tokenize(line, SourceId::repl()) else {
+            return Cow::Borrowed(line);
+        };
+
+        // token spans count chars, so find the byte offset of each char
+        let offsets: Vec<usize> = line
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain([line.len()])
+            .collect();
+
+        let mut highlighted = String::with_capacity(line.len());
+        let mut end = 0;
+        for token in tokens {
+            let color = match token.inner() {
+                Token::If | Token::Then | Token::Else | Token::Let | Token::In | Token::For => {
+                    KEYWORD
+                }
+                Token::Null | Token::Boolean(_) | Token::Number(_) => LITERAL,
+                Token::String(_) => STRING,
+                Token::Invalid(_) => INVALID,
+                _ => continue,
+            };
+            let span = token.span();
+            let (Some(&start), Some(&token_end)) =
+                (offsets.get(span.start()), offsets.get(span.end()))
+            else {
+                continue;
+            };
+            highlighted.push_str(&line[end..start]);
+            highlighted.push_str(color);
+            highlighted.push_str(&line[start..token_end]);
+            highlighted.push_str(RESET);
+            end = token_end;
+        }
+        highlighted.push_str(&line[end..]);
+
+        Cow::Owned(highlighted)
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize, _kind: CmdKind) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rustyline::highlight::Highlighter;
+
+    use super::ReplHelper;
+    use crate::session::Session;
+
+    fn helper() -> ReplHelper {
+        let mut session = Session::new();
+        session.run("config = { port: 80, path: \"/\", nested: { a: 1 } }");
+        session.run("count = 1");
+        ReplHelper::new(session.env())
+    }
+
+    #[test]
+    fn complete_variables_and_keywords() {
+        let helper = helper();
+        assert_eq!(
+            helper.completions("con", 3),
+            (0, vec!["config".into(), "contains".into()])
+        );
+        assert_eq!(helper.completions("1 + th", 6), (4, vec!["then".into()]));
+        // only the word before the cursor
+        assert_eq!(helper.completions("spl + 1", 3), (0, vec!["split".into()]));
+    }
+
+    #[test]
+    fn complete_keys() {
+        let helper = helper();
+        assert_eq!(
+            helper.completions("config.p", 8),
+            (7, vec!["path".into(), "port".into()])
+        );
+        assert_eq!(
+            helper.completions("[config.nested.", 15),
+            (15, vec!["a".into()])
+        );
+        assert_eq!(helper.completions("count.", 6), (6, vec![]));
+    }
+
+    #[test]
+    fn highlight() {
+        let helper = helper();
+        assert_eq!(
+            helper.highlight("if x then \"é\" else null", 0),
+            "\x1b[35mif\x1b[0m x \x1b[35mthen\x1b[0m \x1b[32m\"é\"\x1b[0m \x1b[35melse\x1b[0m \x1b[33mnull\x1b[0m"
+        );
+        assert_eq!(helper.highlight("a + b", 0), "a + b");
+    }
+}
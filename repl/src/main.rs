@@ -1,54 +1,50 @@
-use std::cell::RefCell;
-use std::rc::Rc;
+mod command;
+mod helper;
+mod session;
+mod statement;
 
 use rustyline::error::ReadlineError;
-use rustyline::{DefaultEditor, Result};
+use rustyline::{Editor, Result};
 
-use rimu::{evaluate_expression, parse_expression, Environment, ErrorReport, SourceId};
+use crate::{helper::ReplHelper, session::Session, statement::opens_block};
 
 fn main() -> Result<()> {
-    // `()` can be used when no completer is required
-    let mut rl = DefaultEditor::new()?;
+    let mut session = Session::new();
+
+    let mut rl = Editor::new()?;
+    rl.set_helper(Some(ReplHelper::new(session.env())));
     if rl.load_history(".repl-history.txt").is_err() {
         println!("No previous history.");
     }
 
-    let env = Environment::new();
-    let env = Rc::new(RefCell::new(env));
-
     loop {
         let readline = rl.readline(">> ");
         match readline {
             Ok(line) => {
-                rl.add_history_entry(line.as_str())?;
-
-                let (expr, errors) = parse_expression(line.as_str(), SourceId::repl());
+                let mut entry = line;
 
-                if !errors.is_empty() {
-                    for error in errors {
-                        let report: ErrorReport = error.into();
-                        report.display(line.as_str(), SourceId::repl());
+                if opens_block(&entry) {
+                    loop {
+                        match rl.readline(".. ") {
+                            // a blank line or Ctrl-D ends the entry
+                            Ok(line) if line.trim().is_empty() => break,
+                            Err(ReadlineError::Eof) => break,
+                            Ok(line) => {
+                                entry.push('\n');
+                                entry.push_str(&line);
+                            }
+                            Err(ReadlineError::Interrupted) => {
+                                entry.clear();
+                                break;
+                            }
+                            Err(err) => return Err(err),
+                        }
                     }
-                    continue;
                 }
-                let Some(expr) = expr else {
-                    println!("No expression.");
-                    continue;
-                };
 
-                // println!("Expression: {}", expr);
-
-                let value = match evaluate_expression(&expr, env.clone()) {
-                    Ok(value) => value,
-                    Err(error) => {
-                        let report: ErrorReport = error.into();
-                        report.display(line.as_str(), SourceId::repl());
-                        continue;
-                    }
-                };
-                let value = value.into_inner();
+                rl.add_history_entry(entry.as_str())?;
 
-                println!("{}", value)
+                session.run(entry.as_str());
             }
             Err(ReadlineError::Interrupted) => {
                 println!("CTRL-C");
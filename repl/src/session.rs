@@ -0,0 +1,336 @@
+use std::{cell::RefCell, fs, path::PathBuf, rc::Rc, time::Instant};
+
+use rimu::{
+    create_stdlib, evaluate, parse, tokenize, Environment, ErrorReport, SerdeValue, SourceId,
+    Spanned, SpannedBlock, SpannedValue, Value,
+};
+use rimu_format::Format;
+
+use crate::{command::Command, statement::split_binding};
+
+const HELP: &str = "\
+Enter an expression to evaluate it, or bind a name for later entries with
+`name = expr` or `let name: expr`. A line which opens an indented block
+continues until a blank line.
+
+Commands:
+  :env              List bindings
+  :unset NAME...    Remove bindings
+  :load PATH        Evaluate a file, and bind each key of the resulting object
+  :reload           Load every loaded file again
+  :format FORMAT    Print results as display (the default), yaml, json, toml,
+                    hcl, ini, dotenv, xml, or nix
+  :type EXPR        Show the type of a value
+  :ast EXPR         Show the syntax tree of an entry
+  :tokens EXPR      Show the tokens of a line
+  :time EXPR        Evaluate an entry, and show how long it took
+  :help             Show this help";
+
+fn stdlib() -> Environment {
+    Environment::from_object(&create_stdlib(), None).expect("stdlib is an object")
+}
+
+/// The state of a REPL session: the bindings made so far, and the source of
+/// every entry, so errors can point back into earlier entries.
+pub(crate) struct Session {
+    env: Rc<RefCell<Environment>>,
+    /// The parent of `env`, replaced before each evaluation so state kept by
+    /// the stdlib, such as compiled regexes, doesn't outlive an entry.
+    stdlib: Rc<RefCell<Environment>>,
+    sources: Vec<(SourceId, String)>,
+    loaded: Vec<PathBuf>,
+    entries: usize,
+    /// How to print results, or `None` to use [`Value`]'s `Display`.
+    format: Option<Format>,
+}
+
+impl Session {
+    pub(crate) fn new() -> Self {
+        let stdlib = Rc::new(RefCell::new(stdlib()));
+        let env = Environment::new_with_parent(stdlib.clone());
+        Self {
+            env: Rc::new(RefCell::new(env)),
+            stdlib,
+            sources: Vec::new(),
+            loaded: Vec::new(),
+            entries: 0,
+            format: None,
+        }
+    }
+
+    /// The session environment, with the stdlib as its parent.
+    pub(crate) fn env(&self) -> Rc<RefCell<Environment>> {
+        self.env.clone()
+    }
+
+    pub(crate) fn run(&mut self, input: &str) {
+        if input.trim().is_empty() {
+            return;
+        }
+
+        if let Some(command) = input.trim().strip_prefix(':') {
+            self.command(command);
+            return;
+        }
+
+        let source = self.add_entry(input);
+
+        if let Some((name, code)) = split_binding(input) {
+            let Some(value) = self.evaluate(&code, source) else {
+                return;
+            };
+            // a block object skips null values, so a missing key is null
+            let value = match value.inner() {
+                Value::Object(object) => object.get(name).cloned(),
+                _ => None,
+            }
+            .unwrap_or_else(|| Spanned::new(Value::Null, value.span()));
+            self.env.borrow_mut().insert(name, value);
+        } else if let Some(value) = self.evaluate(input, source) {
+            self.print(value.into_inner());
+        }
+    }
+
+    fn print(&self, value: Value) {
+        let Some(format) = self.format else {
+            println!("{}", value);
+            return;
+        };
+        let value: SerdeValue = value.into();
+        match rimu_format::to_string_pretty(&value, format) {
+            Ok(output) => println!("{}", output.trim_end()),
+            Err(error) => println!("Error: {}", error),
+        }
+    }
+
+    fn command(&mut self, command: &str) {
+        let command = match Command::parse(command) {
+            Ok(command) => command,
+            Err(message) => {
+                println!("{}", message);
+                return;
+            }
+        };
+        match command {
+            Command::Env => {
+                for (name, value) in self.env.borrow().iter_local() {
+                    println!("{} = {}", name, value.inner());
+                }
+            }
+            Command::Unset(names) => {
+                for name in names {
+                    if self.env.borrow_mut().remove(name).is_none() {
+                        println!("Not bound: {}", name);
+                    }
+                }
+            }
+            Command::Load(path) => {
+                let path = PathBuf::from(path);
+                if self.load(&path) && !self.loaded.contains(&path) {
+                    self.loaded.push(path);
+                }
+            }
+            Command::Reload => {
+                if self.loaded.is_empty() {
+                    println!("Nothing loaded.");
+                }
+                for path in self.loaded.clone() {
+                    self.load(&path);
+                }
+            }
+            Command::Format(format) => self.format = format,
+            Command::Type(code) => self.show_type(code),
+            Command::Ast(code) => self.show_ast(code),
+            Command::Tokens(code) => self.show_tokens(code),
+            Command::Time(code) => self.time(code),
+            Command::Help => println!("{}", HELP),
+        }
+    }
+
+    fn show_type(&mut self, code: &str) {
+        let source = self.add_entry(code);
+        if let Some(value) = self.evaluate(code, source) {
+            println!("{}", value.inner().type_name());
+        }
+    }
+
+    fn show_ast(&mut self, code: &str) {
+        let source = self.add_entry(code);
+        if let Some(block) = self.parse(code, source) {
+            println!("{:#?}", block);
+        }
+    }
+
+    fn show_tokens(&mut self, code: &str) {
+        let source = self.add_entry(code);
+        let (tokens, errors) = tokenize(code, source);
+        for error in errors {
+            self.report(error.into());
+        }
+        for token in tokens.into_iter().flatten() {
+            println!("{:?}  {:?}", token.span().range(), token.inner());
+        }
+    }
+
+    /// Evaluate an entry, showing how long parsing and evaluating each took.
+    fn time(&mut self, code: &str) {
+        let source = self.add_entry(code);
+
+        let start = Instant::now();
+        let Some(block) = self.parse(code, source) else {
+            return;
+        };
+        let parsed = start.elapsed();
+
+        self.reset_stdlib();
+        let start = Instant::now();
+        let value = evaluate(&block, self.env.clone());
+        let evaluated = start.elapsed();
+
+        match value {
+            Ok(value) => self.print(value.into_inner()),
+            Err(error) => self.report(error.into()),
+        }
+        println!("parse: {:?}, evaluate: {:?}", parsed, evaluated);
+    }
+
+    /// Evaluate a file, and bind each key of the resulting object. Returns
+    /// whether the file was loaded.
+    fn load(&mut self, path: &PathBuf) -> bool {
+        let code = match fs::read_to_string(path) {
+            Ok(code) => code,
+            Err(error) => {
+                println!("Failed to read {}: {}", path.display(), error);
+                return false;
+            }
+        };
+
+        let source = SourceId::from_path(path);
+        self.add_source(source.clone(), &code);
+
+        let Some(value) = self.evaluate(&code, source) else {
+            return false;
+        };
+        let Value::Object(object) = value.into_inner() else {
+            println!(
+                "Failed to load {}: expected the file to evaluate to an object",
+                path.display()
+            );
+            return false;
+        };
+
+        let mut env = self.env.borrow_mut();
+        for (name, value) in object.iter() {
+            env.insert(name.clone(), value.clone());
+        }
+        println!("Loaded {} bindings from {}", object.len(), path.display());
+        true
+    }
+
+    /// Number a new entry, and keep its source.
+    fn add_entry(&mut self, code: &str) -> SourceId {
+        self.entries += 1;
+        let source = SourceId::from(format!("repl:{}", self.entries));
+        self.add_source(source.clone(), code);
+        source
+    }
+
+    /// Keep the source of an entry or file, replacing any previous source
+    /// with the same id.
+    fn add_source(&mut self, source: SourceId, code: &str) {
+        self.sources.retain(|(id, _)| id != &source);
+        self.sources.push((source, code.to_owned()));
+    }
+
+    /// Parse a block, displaying any errors.
+    fn parse(&self, code: &str, source: SourceId) -> Option<SpannedBlock> {
+        let (block, errors) = parse(code, source);
+
+        if !errors.is_empty() {
+            for error in errors {
+                self.report(error.into());
+            }
+            return None;
+        }
+        if block.is_none() {
+            println!("No block.");
+        }
+        block
+    }
+
+    /// Parse and evaluate a block, displaying any errors.
+    fn evaluate(&self, code: &str, source: SourceId) -> Option<SpannedValue> {
+        let block = self.parse(code, source)?;
+
+        self.reset_stdlib();
+        match evaluate(&block, self.env.clone()) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.report(error.into());
+                None
+            }
+        }
+    }
+
+    fn reset_stdlib(&self) {
+        *self.stdlib.borrow_mut() = stdlib();
+    }
+
+    fn report(&self, report: ErrorReport) {
+        report.display_sources(self.sources.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs};
+
+    use pretty_assertions::assert_eq;
+    use rimu::{SerdeValue, Value};
+
+    use super::Session;
+
+    fn get(session: &Session, keys: Vec<&str>) -> Option<SerdeValue> {
+        let value = session.env.borrow().get_in(keys)?;
+        Some(value.into_inner().into())
+    }
+
+    fn number(number: i64) -> Option<SerdeValue> {
+        Some(Value::Number(number.into()).into())
+    }
+
+    #[test]
+    fn multi_line_binding() {
+        let mut session = Session::new();
+        session.run("config =\n  port: 80\n  host: \"localhost\"");
+        session.run("ports =\n  - 80\n  - config.port + 1");
+
+        assert_eq!(get(&session, vec!["config", "port"]), number(80));
+        assert_eq!(
+            get(&session, vec!["ports"]),
+            Some(SerdeValue::List(vec![
+                number(80).unwrap(),
+                number(81).unwrap()
+            ]))
+        );
+    }
+
+    #[test]
+    fn load_and_reload() {
+        let path = temp_dir().join(format!("rimu-repl-load-{}.rimu", std::process::id()));
+        fs::write(&path, "a: 1\nb: 2\n").unwrap();
+
+        let mut session = Session::new();
+        session.run(&format!(":load {}", path.display()));
+        assert_eq!(get(&session, vec!["a"]), number(1));
+        assert_eq!(get(&session, vec!["b"]), number(2));
+
+        fs::write(&path, "a: 3\n").unwrap();
+        session.run(":reload");
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(get(&session, vec!["a"]), number(3));
+        assert_eq!(get(&session, vec!["b"]), number(2));
+        assert_eq!(session.loaded, vec![path]);
+    }
+}
@@ -0,0 +1,142 @@
+const INLINE_WHITESPACE: [char; 2] = [' ', '\t'];
+
+/// The words which are tokens of their own, rather than identifiers.
+pub(crate) const KEYWORDS: [&str; 9] = [
+    "null", "true", "false", "if", "then", "else", "let", "in", "for",
+];
+
+/// Split a binding statement, either `name = expr` or `let name: expr`, into
+/// the name and the code for a block which evaluates to `{ name: expr }`.
+///
+/// The code has the `name =` or `let name:` prefix rewritten to `name:` padded
+/// with spaces, so spans in the expression still line up with the input. The
+/// expression may be a block on the following lines.
+pub(crate) fn split_binding(input: &str) -> Option<(&str, String)> {
+    let trimmed = input.trim_start();
+    // the binding must start on the first line, unlike a `let` block
+    let (rest, is_let) = match trimmed.strip_prefix("let") {
+        Some(rest) if rest.starts_with(INLINE_WHITESPACE) => {
+            (rest.trim_start_matches(INLINE_WHITESPACE), true)
+        }
+        _ => (trimmed, false),
+    };
+
+    let name_len = identifier_len(rest)?;
+    let name = &rest[..name_len];
+    if KEYWORDS.contains(&name) {
+        return None;
+    }
+
+    let after_name = rest[name_len..].trim_start_matches(INLINE_WHITESPACE);
+    let value = if is_let {
+        after_name.strip_prefix(':')?
+    } else {
+        // not `==` (equality) or `=>` (a function)
+        after_name
+            .strip_prefix('=')
+            .filter(|value| !value.starts_with(['=', '>']))?
+    };
+
+    let padding = input.len() - value.len() - name.len() - 1;
+    Some((name, format!("{}:{}{}", name, " ".repeat(padding), value)))
+}
+
+/// Whether a line starts an indented block, so the entry should continue on
+/// the following lines (until a blank line).
+pub(crate) fn opens_block(line: &str) -> bool {
+    let line = line.trim();
+
+    if let Some((name, code)) = split_binding(line) {
+        let value = code[name.len() + 1..].trim();
+        return value.is_empty() || opens_block_value(value);
+    }
+
+    opens_block_value(line)
+}
+
+fn opens_block_value(line: &str) -> bool {
+    let first_word = line.split_whitespace().next().unwrap_or_default();
+    let rest = line[identifier_len(line).unwrap_or(0)..].trim_start();
+
+    line.ends_with(':')
+        || line.ends_with("=>")
+        || ["let", "if", "then", "else", "in"].contains(&first_word)
+        || line == "-"
+        || line.starts_with("- ")
+        // an object entry, such as `key: value`
+        || rest.starts_with(": ")
+}
+
+/// The length of the identifier at the start of the input, if any.
+fn identifier_len(input: &str) -> Option<usize> {
+    let mut chars = input.chars();
+    let first = chars.next()?;
+    if !(first.is_ascii_alphabetic() || first == '_' || first == '$') {
+        return None;
+    }
+    let rest = chars
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .count();
+    Some(1 + rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::split_binding;
+
+    #[test]
+    fn split_binding_pads_to_the_input() {
+        for (input, name, code) in [
+            ("x = 1 + 2", "x", "x:  1 + 2"),
+            ("let y: 3", "y", "y:     3"),
+            ("  z = 1", "z", "z:    1"),
+            ("letter=[1]", "letter", "letter:[1]"),
+            ("f = (a) => a", "f", "f:  (a) => a"),
+        ] {
+            let actual = split_binding(input);
+            assert_eq!(actual, Some((name, code.to_string())), "{:?}", input);
+            // so spans in the expression line up with the input
+            assert_eq!(code.len(), input.len(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn split_binding_not_a_binding() {
+        for input in [
+            "x == 1",
+            "x => x",
+            "if = 1",
+            "let x = 1",
+            "1 + 2",
+            "x",
+            "a: 1",
+        ] {
+            assert_eq!(split_binding(input), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn opens_block() {
+        for line in [
+            "x =",
+            "let x:",
+            "config:",
+            "f = (a) =>",
+            "x = if ready",
+            "let",
+            "in",
+            "if ready",
+            "then",
+            "else",
+            "- 1",
+            "a: 1",
+        ] {
+            assert!(super::opens_block(line), "{:?}", line);
+        }
+        for line in ["x = 1", "1 + 2", "{ a: 1 }", "x == 1", ""] {
+            assert!(!super::opens_block(line), "{:?}", line);
+        }
+    }
+}
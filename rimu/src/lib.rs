@@ -1,3 +1,5 @@
+mod select;
+
 pub use rimu_ast::{
     BinaryOperator, Block, Expression, SpannedBlock, SpannedExpression, UnaryOperator,
 };
@@ -5,10 +7,15 @@ pub use rimu_eval::{
     call, evaluate_block as evaluate, evaluate_block, evaluate_expression, EvalError,
 };
 pub use rimu_meta::{ErrorReport, ErrorReports, SourceId, Span, Spanned};
-pub use rimu_parse::{parse_block, parse_block as parse, parse_expression, Error as ParseError};
+pub use rimu_parse::{
+    parse_block, parse_block as parse, parse_expression, tokenize, Error as ParseError,
+    SpannedToken, Token,
+};
 pub use rimu_stdlib::create_stdlib;
 pub use rimu_value::{
     convert, from_serde_value, to_serde_value, Environment, EnvironmentError, Function,
-    FunctionBody, Number, SerdeValue, SerdeValueError, SerdeValueList, SerdeValueObject, Value,
-    ValueList, ValueObject,
+    FunctionBody, Number, SerdeValue, SerdeValueError, SerdeValueList, SerdeValueObject,
+    SpannedValue, Value, ValueList, ValueObject,
 };
+
+pub use select::select;
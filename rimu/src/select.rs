@@ -0,0 +1,27 @@
+use std::{cell::RefCell, rc::Rc};
+
+use rimu_ast::SpannedExpression;
+use rimu_eval::{evaluate_expression, EvalError};
+use rimu_value::{Environment, SpannedValue, Value};
+
+/// Evaluate a `selector` expression against a rendered `root` value, such as
+/// `services.api.env` or `$.items[0]`.
+///
+/// The root is bound to `$`, and if the root is an object, each of its keys is
+/// also bound as a variable. Any other variables are looked up in `env`.
+pub fn select(
+    root: &SpannedValue,
+    selector: &SpannedExpression,
+    env: Rc<RefCell<Environment>>,
+) -> Result<SpannedValue, EvalError> {
+    let mut select_env = Environment::new_with_parent(env);
+    if let Value::Object(object) = root.inner() {
+        for (key, value) in object.iter() {
+            select_env.insert(key.clone(), value.clone());
+        }
+    }
+    select_env.insert("$", root.clone());
+    let select_env = Rc::new(RefCell::new(select_env));
+
+    evaluate_expression(selector, select_env)
+}
@@ -0,0 +1,71 @@
+use std::{cell::RefCell, rc::Rc};
+
+use pretty_assertions::assert_eq;
+use rimu::{
+    evaluate, parse, parse_expression, select, Environment, EvalError, SerdeValue, SourceId,
+};
+
+fn select_code(code: &str, selector: &str) -> Result<SerdeValue, EvalError> {
+    let (block, errors) = parse(code, SourceId::empty());
+    assert!(errors.is_empty(), "parse errors: {errors:?}");
+    let env = Rc::new(RefCell::new(Environment::new()));
+    let root = evaluate(&block.expect("block parsed"), env.clone())?;
+
+    let (selector, errors) = parse_expression(selector, SourceId::empty());
+    assert!(errors.is_empty(), "parse errors: {errors:?}");
+    let value = select(&root, &selector.expect("selector parsed"), env)?;
+
+    Ok(value.into_inner().into())
+}
+
+#[test]
+fn key_path() {
+    let code = "
+services:
+  api:
+    env:
+      PORT: 8080
+";
+
+    let actual = select_code(code, "services.api.env").unwrap();
+
+    let expected: SerdeValue = serde_yaml::from_str("PORT: 8080").unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn root() {
+    let code = r#"
+- "a"
+- "b"
+- "c"
+"#;
+
+    let actual = select_code(code, "$[1:]").unwrap();
+
+    let expected: SerdeValue = serde_yaml::from_str("[b, c]").unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn expression() {
+    let code = "
+replicas: 2
+";
+
+    let actual = select_code(code, "replicas * 2").unwrap();
+
+    let expected: SerdeValue = serde_yaml::from_str("4").unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn missing_key() {
+    let code = "
+a: 1
+";
+
+    let actual = select_code(code, "$.b");
+
+    assert!(matches!(actual, Err(EvalError::KeyNotFound { .. })));
+}
@@ -0,0 +1,65 @@
+//! Helpers to check the arguments of native functions, and build their
+//! results.
+
+use rimu_meta::{Span, Spanned};
+use rimu_value::{EvalError, Number, SpannedValue, Value, ValueList};
+
+pub(crate) fn type_error(arg: &SpannedValue, expected: &str) -> EvalError {
+    EvalError::TypeError {
+        span: arg.span(),
+        expected: expected.into(),
+        got: Box::new(arg.clone().into()),
+    }
+}
+
+pub(crate) fn invalid_argument(arg: &SpannedValue, message: impl Into<String>) -> EvalError {
+    EvalError::InvalidArgument {
+        span: arg.span(),
+        message: message.into(),
+    }
+}
+
+pub(crate) fn string_value(string: impl Into<String>, span: Span) -> SpannedValue {
+    Spanned::new(Value::String(string.into()), span)
+}
+
+pub(crate) fn string(arg: &SpannedValue) -> Result<&str, EvalError> {
+    match arg.inner() {
+        Value::String(string) => Ok(string),
+        _ => Err(type_error(arg, "string")),
+    }
+}
+
+pub(crate) fn list(arg: &SpannedValue) -> Result<&ValueList, EvalError> {
+    match arg.inner() {
+        Value::List(list) => Ok(list),
+        _ => Err(type_error(arg, "list")),
+    }
+}
+
+pub(crate) fn number(arg: &SpannedValue) -> Result<Number, EvalError> {
+    match arg.inner() {
+        Value::Number(number) => Ok(*number),
+        _ => Err(type_error(arg, "number")),
+    }
+}
+
+/// A count or length, which must be a zero or positive integer.
+pub(crate) fn count(arg: &SpannedValue) -> Result<usize, EvalError> {
+    match arg.inner() {
+        Value::Number(number) if number.fract().is_zero() => number
+            .to_usize()
+            .ok_or_else(|| type_error(arg, "zero or positive integer")),
+        _ => Err(type_error(arg, "zero or positive integer")),
+    }
+}
+
+/// Check a value has no functions within it, so it can be written as data.
+pub(crate) fn data(arg: &SpannedValue) -> Result<(), EvalError> {
+    match arg.inner() {
+        Value::Function(_) => Err(type_error(arg, "any value except a function")),
+        Value::List(list) => list.iter().try_for_each(data),
+        Value::Object(object) => object.values().try_for_each(data),
+        _ => Ok(()),
+    }
+}
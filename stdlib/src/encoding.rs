@@ -0,0 +1,362 @@
+//! Functions to encode and decode strings, and to convert values to and from
+//! JSON, YAML and TOML documents.
+//!
+//! Encodings apply to the UTF-8 bytes of a string, and decoding must give
+//! valid UTF-8.
+
+use rimu_format::Format;
+use rimu_meta::Span;
+use rimu_value::{EvalError, Function, SerdeValue, SerdeValueObject, SpannedValue};
+
+use crate::{args, native_function};
+
+pub(crate) fn insert(lib: &mut SerdeValueObject) {
+    lib.insert("base64_encode".into(), base64_encode().into());
+    lib.insert("base64_decode".into(), base64_decode().into());
+    lib.insert("hex_encode".into(), hex_encode().into());
+    lib.insert("hex_decode".into(), hex_decode().into());
+    lib.insert("url_encode".into(), url_encode().into());
+    lib.insert("url_decode".into(), url_decode().into());
+    lib.insert("to_json".into(), to_json().into());
+    lib.insert("to_yaml".into(), to_yaml().into());
+    lib.insert("to_toml".into(), to_toml().into());
+    lib.insert("parse_json".into(), parse_json().into());
+    lib.insert("parse_yaml".into(), parse_yaml().into());
+    lib.insert("parse_toml".into(), parse_toml().into());
+}
+
+fn utf8(arg: &SpannedValue, bytes: Vec<u8>) -> Result<String, EvalError> {
+    String::from_utf8(bytes)
+        .map_err(|_| args::invalid_argument(arg, "Decoded bytes are not valid UTF-8"))
+}
+
+const BASE64_STANDARD: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The base64 alphabet, and whether to pad, from an optional `"standard"` or
+/// `"url"` argument.
+fn base64_variant(arg: Option<&SpannedValue>) -> Result<(&'static [u8; 64], bool), EvalError> {
+    let Some(arg) = arg else {
+        return Ok((BASE64_STANDARD, true));
+    };
+    match args::string(arg)? {
+        "standard" => Ok((BASE64_STANDARD, true)),
+        "url" => Ok((BASE64_URL, false)),
+        other => Err(args::invalid_argument(
+            arg,
+            format!(
+                "Unknown base64 variant: {:?}, expected \"standard\" or \"url\"",
+                other
+            ),
+        )),
+    }
+}
+
+fn base64_encode_bytes(bytes: &[u8], alphabet: &[u8; 64], pad: bool) -> String {
+    let mut string = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | (*byte as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            string.push(alphabet[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+        if pad {
+            for _ in chunk.len()..3 {
+                string.push('=');
+            }
+        }
+    }
+    string
+}
+
+fn base64_decode_bytes(string: &str, alphabet: &[u8; 64]) -> Option<Vec<u8>> {
+    let string = string.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(string.len() * 3 / 4);
+    let (mut n, mut bits) = (0u32, 0);
+    for c in string.bytes() {
+        let sextet = alphabet.iter().position(|a| *a == c)? as u32;
+        n = n << 6 | sextet;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((n >> bits) as u8);
+        }
+    }
+    // a lone character is never valid, and unused bits must be zero
+    if bits == 6 || n & ((1 << bits) - 1) != 0 {
+        return None;
+    }
+    Some(bytes)
+}
+
+/// Encode a string as base64, with an optional variant: `"standard"` (the
+/// default), or `"url"` for the URL-safe alphabet without padding.
+pub fn base64_encode() -> Function {
+    native_function("base64_encode", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        let (alphabet, pad) = base64_variant(args.get(1))?;
+        Ok(args::string_value(
+            base64_encode_bytes(string.as_bytes(), alphabet, pad),
+            span,
+        ))
+    })
+}
+
+/// Decode a base64 string, with an optional variant: `"standard"` (the
+/// default), or `"url"` for the URL-safe alphabet. Padding is optional.
+pub fn base64_decode() -> Function {
+    native_function("base64_decode", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        let (alphabet, _) = base64_variant(args.get(1))?;
+        let bytes = base64_decode_bytes(string, alphabet)
+            .ok_or_else(|| args::invalid_argument(&args[0], "Invalid base64"))?;
+        Ok(args::string_value(utf8(&args[0], bytes)?, span))
+    })
+}
+
+/// Encode a string as lower case hexadecimal.
+pub fn hex_encode() -> Function {
+    native_function("hex_encode", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        let hex: String = string.bytes().map(|byte| format!("{:02x}", byte)).collect();
+        Ok(args::string_value(hex, span))
+    })
+}
+
+/// A byte from two hex digits. Unlike `u8::from_str_radix`, this doesn't
+/// accept a sign.
+fn hex_byte(hex: &[u8]) -> Option<u8> {
+    let digit = |c: u8| (c as char).to_digit(16);
+    match hex {
+        [high, low] => Some((digit(*high)? * 16 + digit(*low)?) as u8),
+        _ => None,
+    }
+}
+
+/// Decode a hexadecimal string, in upper or lower case.
+pub fn hex_decode() -> Function {
+    native_function("hex_decode", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        let invalid = || args::invalid_argument(&args[0], "Invalid hex");
+        if string.len() % 2 != 0 {
+            return Err(invalid());
+        }
+        let bytes = string
+            .as_bytes()
+            .chunks(2)
+            .map(hex_byte)
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid)?;
+        Ok(args::string_value(utf8(&args[0], bytes)?, span))
+    })
+}
+
+/// Percent-encode a string for use in a URL, leaving only unreserved
+/// characters (`A-Z`, `a-z`, `0-9`, `-`, `.`, `_` and `~`) as they are.
+pub fn url_encode() -> Function {
+    native_function("url_encode", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        let mut encoded = String::with_capacity(string.len());
+        for byte in string.bytes() {
+            if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+                encoded.push(byte as char);
+            } else {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+        Ok(args::string_value(encoded, span))
+    })
+}
+
+/// Decode a percent-encoded string.
+pub fn url_decode() -> Function {
+    native_function("url_decode", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        let mut bytes = Vec::with_capacity(string.len());
+        let mut rest = string.as_bytes();
+        while let Some((byte, after)) = rest.split_first() {
+            if *byte == b'%' {
+                let decoded = after
+                    .get(..2)
+                    .and_then(hex_byte)
+                    .ok_or_else(|| args::invalid_argument(&args[0], "Invalid percent-encoding"))?;
+                bytes.push(decoded);
+                rest = &after[2..];
+            } else {
+                bytes.push(*byte);
+                rest = after;
+            }
+        }
+        Ok(args::string_value(utf8(&args[0], bytes)?, span))
+    })
+}
+
+fn serialize(span: Span, args: &[SpannedValue], format: Format) -> Result<SpannedValue, EvalError> {
+    args::data(&args[0])?;
+    let value = SerdeValue::from(args[0].clone());
+    let string = rimu_format::to_string(&value, format).map_err(|error| {
+        args::invalid_argument(&args[0], format!("Cannot convert to {}: {}", format, error))
+    })?;
+    Ok(args::string_value(string, span))
+}
+
+fn deserialize<E: std::fmt::Display>(
+    span: Span,
+    arg: &SpannedValue,
+    format: Format,
+    parse: impl FnOnce(&str) -> Result<SerdeValue, E>,
+) -> Result<SpannedValue, EvalError> {
+    let string = args::string(arg)?;
+    let value = parse(string)
+        .map_err(|error| args::invalid_argument(arg, format!("Invalid {}: {}", format, error)))?;
+    Ok(value.with_span(span))
+}
+
+/// Convert a value to a JSON string, on one line.
+pub fn to_json() -> Function {
+    native_function("to_json", &["value"], |span, args| {
+        serialize(span, args, Format::Json)
+    })
+}
+
+/// Convert a value to a YAML string.
+pub fn to_yaml() -> Function {
+    native_function("to_yaml", &["value"], |span, args| {
+        serialize(span, args, Format::Yaml)
+    })
+}
+
+/// Convert an object to a TOML string.
+pub fn to_toml() -> Function {
+    native_function("to_toml", &["value"], |span, args| {
+        serialize(span, args, Format::Toml)
+    })
+}
+
+/// Parse a JSON string into a value.
+pub fn parse_json() -> Function {
+    native_function("parse_json", &["string"], |span, args| {
+        deserialize(span, &args[0], Format::Json, |string| {
+            serde_json::from_str(string)
+        })
+    })
+}
+
+/// Parse a YAML string into a value.
+pub fn parse_yaml() -> Function {
+    native_function("parse_yaml", &["string"], |span, args| {
+        deserialize(span, &args[0], Format::Yaml, |string| {
+            serde_yaml::from_str(string)
+        })
+    })
+}
+
+/// Parse a TOML string into an object.
+pub fn parse_toml() -> Function {
+    native_function("parse_toml", &["string"], |span, args| {
+        deserialize(span, &args[0], Format::Toml, |string| {
+            toml::from_str(string)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rimu_value::EvalError;
+
+    use crate::tests::{assert_err, assert_true, eval_with_stdlib, invalid_argument, type_error};
+
+    #[test]
+    fn base64() {
+        assert_true(r#"base64_encode("") == """#);
+        assert_true(r#"base64_encode("f") == "Zg==""#);
+        assert_true(r#"base64_encode("fo") == "Zm8=""#);
+        assert_true(r#"base64_encode("foo") == "Zm9v""#);
+        assert_true(r#"base64_encode("hunter2!") == "aHVudGVyMiE=""#);
+        assert_true(r#"base64_encode("a?b>", "url") == "YT9iPg""#);
+        assert_true(r#"base64_encode("a?b>") == "YT9iPg==""#);
+        assert_true(r#"base64_decode("aHVudGVyMiE=") == "hunter2!""#);
+        assert_true(r#"base64_decode("aHVudGVyMiE") == "hunter2!""#);
+        assert_true(r#"base64_decode("YT9iPg", "url") == "a?b>""#);
+        assert_true(r#"base64_decode(base64_encode("héllo")) == "héllo""#);
+    }
+
+    #[test]
+    fn hex() {
+        assert_true(r#"hex_encode("hi!") == "686921""#);
+        assert_true(r#"hex_decode("686921") == "hi!" && hex_decode("6A") == "j""#);
+    }
+
+    #[test]
+    fn url() {
+        assert_true(r#"url_encode("a b&c=d/é~") == "a%20b%26c%3Dd%2F%C3%A9~""#);
+        assert_true(r#"url_decode("a%20b%26c%3dd%2F%C3%A9~") == "a b&c=d/é~""#);
+        assert_true(r#"url_decode("a+b") == "a+b""#);
+    }
+
+    #[test]
+    fn to_documents() {
+        assert_true(r#"to_json({ a: [true, null], b: "c" }) == "{\"a\":[true,null],\"b\":\"c\"}""#);
+        assert_true(r#"to_yaml({ a: ["b", "c"] }) == "a:\n- b\n- c\n""#);
+        assert_true(r#"to_toml({ a: "b", c: { d: true } }) == "a = \"b\"\n\n[c]\nd = true\n""#);
+    }
+
+    #[test]
+    fn parse_documents() {
+        assert_true(r#"parse_json("{\"a\": [1, 2.5, null]}") == { a: [1, 2.5, null] }"#);
+        assert_true(r#"parse_yaml("a:\n  - b\n  - c\n") == { a: ["b", "c"] }"#);
+        assert_true(r#"parse_toml("a = 1\n[b]\nc = true\n") == { a: 1, b: { c: true } }"#);
+        assert_true(r#"parse_json(to_json({ a: [1, "b"] })) == { a: [1, "b"] }"#);
+    }
+
+    #[test]
+    fn errors() {
+        for code in [
+            r#"base64_decode("a")"#,
+            r#"base64_decode("a$==")"#,
+            r#"base64_decode("YT9iPg", "web")"#,
+            r#"base64_decode("/w==")"#,
+            r#"hex_decode("abc")"#,
+            r#"hex_decode("zz")"#,
+            r#"hex_decode("+1")"#,
+            r#"hex_decode("-1")"#,
+            r#"hex_decode("é")"#,
+            r#"url_decode("100%")"#,
+            r#"url_decode("%zz")"#,
+            r#"url_decode("%+1")"#,
+            r#"url_decode("%é")"#,
+            r#"to_toml([1, 2])"#,
+            r#"parse_json("{")"#,
+            r#"parse_yaml("a: [")"#,
+            r#"parse_toml("a = ")"#,
+        ] {
+            assert_err(code, invalid_argument);
+        }
+
+        for code in [
+            r#"to_json({ f: (x) => x })"#,
+            r#"to_yaml([1, [split]])"#,
+            r#"to_toml({ a: { b: (x) => x } })"#,
+        ] {
+            assert_err(code, type_error);
+        }
+
+        let actual = eval_with_stdlib(r#"to_json({ a: 1, f: (x) => x })"#);
+        let Err(EvalError::TypeError { span, .. }) = actual else {
+            panic!("expected type error, got: {:?}", actual);
+        };
+        // points at the function
+        assert_eq!((span.start(), span.end()), (19, 27));
+
+        let actual = eval_with_stdlib(r#"parse_json("[1,")"#);
+        let Err(EvalError::InvalidArgument { span, .. }) = actual else {
+            panic!("expected invalid argument, got: {:?}", actual);
+        };
+        // points at the document argument
+        assert_eq!((span.start(), span.end()), (11, 16));
+    }
+}
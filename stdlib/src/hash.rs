@@ -0,0 +1,173 @@
+//! Functions to hash values and derive stable IDs from them.
+//!
+//! A string is hashed as its UTF-8 bytes. Any other value is hashed as its
+//! canonical JSON: objects with sorted keys, numbers without trailing zeros,
+//! and no whitespace. So equal values always give equal hashes, however
+//! they're written.
+
+use md5::Md5;
+use rimu_value::{EvalError, Function, SerdeValueObject, SpannedValue, Value};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{args, native_function};
+
+pub(crate) fn insert(lib: &mut SerdeValueObject) {
+    lib.insert("sha256".into(), sha256().into());
+    lib.insert("sha1".into(), sha1().into());
+    lib.insert("md5".into(), md5().into());
+    lib.insert("blake3".into(), blake3().into());
+    lib.insert("uuid_v5".into(), uuid_v5().into());
+}
+
+/// The bytes to hash for a value.
+fn hash_input(arg: &SpannedValue) -> Result<Vec<u8>, EvalError> {
+    match arg.inner() {
+        Value::String(string) => Ok(string.as_bytes().to_vec()),
+        _ => {
+            let mut json = String::new();
+            canonical_json(arg, &mut json)?;
+            Ok(json.into_bytes())
+        }
+    }
+}
+
+fn canonical_json(value: &SpannedValue, json: &mut String) -> Result<(), EvalError> {
+    match value.inner() {
+        Value::Null => json.push_str("null"),
+        Value::Boolean(boolean) => json.push_str(&boolean.to_string()),
+        Value::Number(number) => json.push_str(&number.normalize().to_string()),
+        Value::String(string) => json_string(string, json),
+        Value::HostPath(path) => json_string(&path.display().to_string(), json),
+        Value::TargetPath(path) => json_string(path.as_str(), json),
+        Value::List(list) => {
+            json.push('[');
+            for (index, item) in list.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                canonical_json(item, json)?;
+            }
+            json.push(']');
+        }
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            json.push('{');
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                json_string(key, json);
+                json.push(':');
+                canonical_json(value, json)?;
+            }
+            json.push('}');
+        }
+        Value::Function(_) => return Err(args::type_error(value, "any value except a function")),
+    }
+    Ok(())
+}
+
+fn json_string(string: &str, json: &mut String) {
+    // serializing a string never fails
+    json.push_str(&serde_json::to_string(string).unwrap_or_default());
+}
+
+fn digest<D: Digest>(arg: &SpannedValue) -> Result<String, EvalError> {
+    let hash = D::digest(hash_input(arg)?);
+    Ok(hash.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// The SHA-256 hash of a value, in hex.
+pub fn sha256() -> Function {
+    native_function("sha256", &["value"], |span, args| {
+        Ok(args::string_value(digest::<Sha256>(&args[0])?, span))
+    })
+}
+
+/// The SHA-1 hash of a value, in hex. Not for security.
+pub fn sha1() -> Function {
+    native_function("sha1", &["value"], |span, args| {
+        Ok(args::string_value(digest::<Sha1>(&args[0])?, span))
+    })
+}
+
+/// The MD5 hash of a value, in hex. Not for security.
+pub fn md5() -> Function {
+    native_function("md5", &["value"], |span, args| {
+        Ok(args::string_value(digest::<Md5>(&args[0])?, span))
+    })
+}
+
+/// The BLAKE3 hash of a value, in hex.
+pub fn blake3() -> Function {
+    native_function("blake3", &["value"], |span, args| {
+        let hash = blake3::hash(&hash_input(&args[0])?);
+        Ok(args::string_value(hash.to_hex().to_string(), span))
+    })
+}
+
+/// A version 5 UUID, from the SHA-1 hash of a name within a namespace. The
+/// namespace is a UUID, or one of `"dns"`, `"url"`, `"oid"` or `"x500"`.
+pub fn uuid_v5() -> Function {
+    native_function("uuid_v5", &["namespace", "name"], |span, args| {
+        let namespace = match args::string(&args[0])? {
+            "dns" => Uuid::NAMESPACE_DNS,
+            "url" => Uuid::NAMESPACE_URL,
+            "oid" => Uuid::NAMESPACE_OID,
+            "x500" => Uuid::NAMESPACE_X500,
+            namespace => {
+                Uuid::parse_str(namespace).map_err(|error| EvalError::InvalidArgument {
+                    span: args[0].span(),
+                    message: format!("Invalid namespace: {}", error),
+                })?
+            }
+        };
+        let name = args::string(&args[1])?;
+        let uuid = Uuid::new_v5(&namespace, name.as_bytes());
+        Ok(args::string_value(uuid.hyphenated().to_string(), span))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{assert_err, assert_true, invalid_argument, type_error};
+
+    #[test]
+    fn string_hashes() {
+        assert_true(
+            r#"sha256("abc") == "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad""#,
+        );
+        assert_true(r#"sha1("abc") == "a9993e364706816aba3e25717850c26c9cd0d89d""#);
+        assert_true(r#"md5("abc") == "900150983cd24fb0d6963f7d28e17f72""#);
+        assert_true(
+            r#"blake3("abc") == "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85""#,
+        );
+    }
+
+    #[test]
+    fn value_hashes() {
+        // canonical JSON is `{"a":[1,"b",null],"c":true}`
+        assert_true(
+            r#"sha256({ c: true, a: [1, "b", null] }) == sha256("{\"a\":[1,\"b\",null],\"c\":true}")"#,
+        );
+        assert_true(r#"sha256({ a: 1.50, b: 2 }) == sha256({ b: 2.0, a: 1.5 })"#);
+        assert_true(r#"md5(1) == md5("1") && md5([1]) != md5(["1"])"#);
+    }
+
+    #[test]
+    fn uuid_v5() {
+        assert_true(r#"uuid_v5("dns", "rust-lang.org") == "c66bbb60-d62e-5f17-a399-3a0bd237c503""#);
+        assert_true(
+            r#"uuid_v5("6ba7b810-9dad-11d1-80b4-00c04fd430c8", "rust-lang.org") == uuid_v5("dns", "rust-lang.org")"#,
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_err(r#"uuid_v5("nope", "a")"#, invalid_argument);
+        assert_err(r#"sha256({ f: (x) => x })"#, type_error);
+    }
+}
@@ -1,4 +1,7 @@
-use std::{cell::RefCell, path::PathBuf, rc::Rc, slice::from_ref};
+use std::{
+    cell::RefCell, cmp::Ordering, iter::Peekable, path::PathBuf, rc::Rc, slice::from_ref,
+    str::Chars,
+};
 
 use rimu_eval::call;
 use rimu_meta::{Span, Spanned};
@@ -8,14 +11,33 @@ use rimu_value::{
 };
 use typed_path::Utf8TypedPathBuf;
 
+mod args;
+mod encoding;
+mod hash;
+mod math;
+mod path;
+mod pattern;
+mod string;
+mod units;
+
 pub fn create_stdlib() -> SerdeValueObject {
     let mut lib = SerdeValueObject::new();
     lib.insert("length".into(), length().into());
     lib.insert("map".into(), map().into());
     lib.insert("range".into(), range().into());
+    lib.insert("sort".into(), sort().into());
+    lib.insert("natural_sort".into(), natural_sort().into());
+    lib.insert("unique".into(), unique().into());
     lib.insert("host_path".into(), host_path().into());
     lib.insert("target_path".into(), target_path().into());
     lib.insert("to_string".into(), to_string().into());
+    string::insert(&mut lib);
+    pattern::insert(&mut lib);
+    math::insert(&mut lib);
+    units::insert(&mut lib);
+    encoding::insert(&mut lib);
+    hash::insert(&mut lib);
+    path::insert(&mut lib);
     lib
 }
 
@@ -23,12 +45,27 @@ fn empty_env() -> Rc<RefCell<Environment>> {
     Rc::new(RefCell::new(Environment::new()))
 }
 
+type NativeResult = Result<SpannedValue, EvalError>;
+
+/// A [`Function`] implemented in Rust, called with at least the given args.
+fn native_function(
+    name: &'static str,
+    args: &[&str],
+    function: impl Fn(Span, &[SpannedValue]) -> NativeResult + 'static,
+) -> Function {
+    Function {
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+        env: empty_env(),
+        body: FunctionBody::Native(NativeFunction::new(name, function)),
+    }
+}
+
 pub fn length() -> Function {
     let function = |span: Span, args: &[Spanned<Value>]| -> Result<SpannedValue, EvalError> {
         let (arg, arg_span) = &args[0].clone().take();
         let value = match arg {
             Value::List(list) => list.len().into(),
-            Value::String(string) => string.len().into(),
+            Value::String(string) => string.chars().count().into(),
             _ => {
                 return Err(EvalError::TypeError {
                     span: arg_span.clone(),
@@ -173,6 +210,151 @@ fn range_op(span: Span, options: RangeOptions) -> Result<SpannedValue, EvalError
     Ok(Spanned::new(Value::List(list), span))
 }
 
+/// Sort a list. Items of the same kind are ordered as by the comparison
+/// operators, and items of different kinds are ordered by kind.
+pub fn sort() -> Function {
+    let function = |span: Span, args: &[Spanned<Value>]| -> Result<SpannedValue, EvalError> {
+        let (arg, arg_span) = &args[0].clone().take();
+        let Value::List(list) = arg else {
+            return Err(EvalError::TypeError {
+                span: arg_span.clone(),
+                expected: "list".into(),
+                got: Box::new(arg.clone().into()),
+            });
+        };
+        let mut unordered = None;
+        let mut list = list.clone();
+        list.sort_by(|a, b| {
+            a.inner().semantic_cmp(b.inner()).unwrap_or_else(|| {
+                unordered.get_or_insert_with(|| a.clone());
+                Ordering::Equal
+            })
+        });
+        if let Some(item) = unordered {
+            let (item, item_span) = item.take();
+            return Err(EvalError::TypeError {
+                span: item_span,
+                expected:
+                    "null | boolean | number | string | host-path | target-path | list | object"
+                        .into(),
+                got: Box::new(item.into()),
+            });
+        }
+        Ok(Spanned::new(Value::List(list), span))
+    };
+    Function {
+        args: vec!["arg".into()],
+        env: empty_env(),
+        body: FunctionBody::Native(NativeFunction::new("sort", function)),
+    }
+}
+
+/// Sort a list of strings in natural order, see [`natural_cmp`].
+pub fn natural_sort() -> Function {
+    let function = |span: Span, args: &[Spanned<Value>]| -> Result<SpannedValue, EvalError> {
+        let (arg, arg_span) = &args[0].clone().take();
+        let Value::List(list) = arg else {
+            return Err(EvalError::TypeError {
+                span: arg_span.clone(),
+                expected: "list".into(),
+                got: Box::new(arg.clone().into()),
+            });
+        };
+        let mut strings = Vec::with_capacity(list.len());
+        for item in list {
+            let Value::String(string) = item.inner() else {
+                return Err(EvalError::TypeError {
+                    span: item.span(),
+                    expected: "string".into(),
+                    got: Box::new(item.clone().into()),
+                });
+            };
+            strings.push((string.clone(), item.span()));
+        }
+        strings.sort_by(|(a, _), (b, _)| natural_cmp(a, b));
+        let list = strings
+            .into_iter()
+            .map(|(string, span)| Spanned::new(Value::String(string), span))
+            .collect();
+        Ok(Spanned::new(Value::List(list), span))
+    };
+    Function {
+        args: vec!["arg".into()],
+        env: empty_env(),
+        body: FunctionBody::Native(NativeFunction::new("natural_sort", function)),
+    }
+}
+
+/// Compare strings in natural order, where runs of ASCII digits compare by
+/// their numeric value (`"v1.9" < "v1.10"`) and everything else compares by
+/// Unicode code point. Doesn't depend on the locale.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        let ordering = match (a_chars.peek(), b_chars.peek()) {
+            // equal up to leading zeros, so fall back to code points
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x = take_digits(&mut a_chars);
+                let y = take_digits(&mut b_chars);
+                let x = x.trim_start_matches('0');
+                let y = y.trim_start_matches('0');
+                x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+            }
+            (Some(x), Some(y)) => {
+                let ordering = x.cmp(y);
+                a_chars.next();
+                b_chars.next();
+                ordering
+            }
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+}
+
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+        digits.push(digit);
+    }
+    digits
+}
+
+/// Remove repeated items from a list, keeping the first of each, with the
+/// same equality as `==`.
+pub fn unique() -> Function {
+    let function = |span: Span, args: &[Spanned<Value>]| -> Result<SpannedValue, EvalError> {
+        let (arg, arg_span) = &args[0].clone().take();
+        let Value::List(list) = arg else {
+            return Err(EvalError::TypeError {
+                span: arg_span.clone(),
+                expected: "list".into(),
+                got: Box::new(arg.clone().into()),
+            });
+        };
+        let mut next_list: Vec<SpannedValue> = Vec::with_capacity(list.len());
+        for item in list {
+            if !next_list
+                .iter()
+                .any(|other| other.inner().semantic_eq(item.inner()))
+            {
+                next_list.push(item.clone());
+            }
+        }
+        Ok(Spanned::new(Value::List(next_list), span))
+    };
+    Function {
+        args: vec!["arg".into()],
+        env: empty_env(),
+        body: FunctionBody::Native(NativeFunction::new("unique", function)),
+    }
+}
+
 /// Construct a [`Value::HostPath`] from a relative string, resolved against
 /// the directory of the source file the call appears in. The resolved path is
 /// absolute when the source id is itself an absolute path, so it can be
@@ -287,7 +469,7 @@ mod tests {
 
     /// Run `code` with the stdlib in scope and a source id whose parent is
     /// `/tmp` — `host_path("./x")` resolves to `/tmp/x`.
-    fn eval_with_stdlib(code: &str) -> Result<Value, EvalError> {
+    pub(crate) fn eval_with_stdlib(code: &str) -> Result<Value, EvalError> {
         let source = SourceId::from("/tmp/test.rimu".to_string());
         let (Some(expr), errors) = parse_expression(code, source.clone()) else {
             panic!("parse failed");
@@ -303,6 +485,34 @@ mod tests {
         rimu_eval::evaluate_expression(&expr, env).map(|v| v.into_inner())
     }
 
+    pub(crate) fn assert_true(code: &str) {
+        assert_eq!(eval_with_stdlib(code), Ok(Value::Boolean(true)), "{}", code);
+    }
+
+    /// Assert `code` fails with an error of the given kind, such as
+    /// [`invalid_argument`].
+    pub(crate) fn assert_err(code: &str, kind: fn(&EvalError) -> bool) {
+        let actual = eval_with_stdlib(code);
+        assert!(
+            matches!(&actual, Err(error) if kind(error)),
+            "{}: {:?}",
+            code,
+            actual
+        );
+    }
+
+    pub(crate) fn invalid_argument(error: &EvalError) -> bool {
+        matches!(error, EvalError::InvalidArgument { .. })
+    }
+
+    pub(crate) fn type_error(error: &EvalError) -> bool {
+        matches!(error, EvalError::TypeError { .. })
+    }
+
+    pub(crate) fn arithmetic(error: &EvalError) -> bool {
+        matches!(error, EvalError::Arithmetic { .. })
+    }
+
     #[test]
     fn host_path_resolves_against_source_dir() {
         let path_fn = host_path();
@@ -418,6 +628,51 @@ mod tests {
         assert_eq!(actual, Value::String("/etc".into()));
     }
 
+    #[test]
+    fn sort_orders_items() {
+        let actual =
+            eval_with_stdlib(r#"sort([3, "b", [1], null, "a", 1]) == [null, 1, 3, "a", "b", [1]]"#)
+                .unwrap();
+        assert_eq!(actual, Value::Boolean(true));
+    }
+
+    #[test]
+    fn sort_errors_on_functions() {
+        let err = eval_with_stdlib(r#"sort([(a) => a, (b) => b])"#).unwrap_err();
+        assert!(matches!(err, EvalError::TypeError { .. }));
+    }
+
+    #[test]
+    fn natural_sort_orders_digits_by_value() {
+        let actual = eval_with_stdlib(
+            r#"natural_sort(["v1.10", "v1.9", "v1.09", "host2", "host10", "Host3"]) == ["Host3", "host2", "host10", "v1.09", "v1.9", "v1.10"]"#,
+        )
+        .unwrap();
+        assert_eq!(actual, Value::Boolean(true));
+    }
+
+    #[test]
+    fn natural_sort_errors_on_non_strings() {
+        let err = eval_with_stdlib(r#"natural_sort(["a", 1])"#).unwrap_err();
+        assert!(matches!(err, EvalError::TypeError { .. }));
+    }
+
+    #[test]
+    fn paths_compare_by_component() {
+        // by bytes, "-" would sort before "/"
+        let actual = eval_with_stdlib(
+            r#"target_path("/a/b") < target_path("/a-b") && host_path("./a/b") < host_path("./a-b")"#,
+        )
+        .unwrap();
+        assert_eq!(actual, Value::Boolean(true));
+    }
+
+    #[test]
+    fn unique_keeps_first_of_each() {
+        let actual = eval_with_stdlib(r#"unique([1, [2], 1.0, [2], 3]) == [1, [2], 3]"#).unwrap();
+        assert_eq!(actual, Value::Boolean(true));
+    }
+
     #[test]
     fn path_plus_path_errors() {
         let err = eval_with_stdlib(r#"host_path("./a") + target_path("/b")"#).unwrap_err();
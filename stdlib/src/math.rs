@@ -0,0 +1,388 @@
+//! Functions on numbers.
+//!
+//! Numbers are decimals with up to 28 significant digits. Results which
+//! don't terminate within that, like `sqrt(2)`, are rounded to fit, while
+//! results which are too large to represent are an error.
+
+use std::str::FromStr;
+
+use rimu_meta::{Span, Spanned};
+use rimu_value::{EvalError, Function, Number, SerdeValueObject, SpannedValue, Value};
+use rust_decimal::{prelude::ToPrimitive, Decimal, MathematicalOps, RoundingStrategy};
+
+use crate::{args, native_function};
+
+pub(crate) fn insert(lib: &mut SerdeValueObject) {
+    lib.insert("min".into(), min().into());
+    lib.insert("max".into(), max().into());
+    lib.insert("abs".into(), abs().into());
+    lib.insert("floor".into(), floor().into());
+    lib.insert("ceil".into(), ceil().into());
+    lib.insert("round".into(), round().into());
+    lib.insert("pow".into(), pow().into());
+    lib.insert("sqrt".into(), sqrt().into());
+    lib.insert("clamp".into(), clamp().into());
+    lib.insert("sum".into(), sum().into());
+    lib.insert("product".into(), product().into());
+    lib.insert("to_int".into(), to_int().into());
+    lib.insert("parse_number".into(), parse_number().into());
+}
+
+fn number_value(number: impl Into<Number>, span: Span) -> SpannedValue {
+    Spanned::new(Value::Number(number.into()), span)
+}
+
+fn numbers(arg: &SpannedValue) -> Result<Vec<Number>, EvalError> {
+    args::list(arg)?.iter().map(args::number).collect()
+}
+
+fn too_large(span: Span) -> EvalError {
+    EvalError::Arithmetic {
+        span,
+        message: "Result is too large to represent".into(),
+    }
+}
+
+/// The smallest number in a list, or `null` if the list is empty.
+pub fn min() -> Function {
+    native_function("min", &["list"], |span, args| {
+        let value = match numbers(&args[0])?.into_iter().min() {
+            Some(number) => Value::Number(number),
+            None => Value::Null,
+        };
+        Ok(Spanned::new(value, span))
+    })
+}
+
+/// The largest number in a list, or `null` if the list is empty.
+pub fn max() -> Function {
+    native_function("max", &["list"], |span, args| {
+        let value = match numbers(&args[0])?.into_iter().max() {
+            Some(number) => Value::Number(number),
+            None => Value::Null,
+        };
+        Ok(Spanned::new(value, span))
+    })
+}
+
+pub fn abs() -> Function {
+    native_function("abs", &["number"], |span, args| {
+        let number = args::number(&args[0])?;
+        Ok(number_value(number.abs(), span))
+    })
+}
+
+pub fn floor() -> Function {
+    native_function("floor", &["number"], |span, args| {
+        let number = args::number(&args[0])?;
+        Ok(number_value(number.floor(), span))
+    })
+}
+
+pub fn ceil() -> Function {
+    native_function("ceil", &["number"], |span, args| {
+        let number = args::number(&args[0])?;
+        Ok(number_value(number.ceil(), span))
+    })
+}
+
+/// Round a number to an optional number of decimal places (default `0`),
+/// with an optional rounding strategy (default `"half_away_from_zero"`).
+pub fn round() -> Function {
+    native_function("round", &["number"], |span, args| {
+        let number = args::number(&args[0])?;
+        let places = match args.get(1) {
+            Some(places) => args::count(places)?,
+            None => 0,
+        };
+        let strategy = match args.get(2) {
+            Some(strategy) => rounding_strategy(strategy)?,
+            None => RoundingStrategy::MidpointAwayFromZero,
+        };
+        // a number never has more than 28 decimal places
+        let places = places.min(28) as u32;
+        Ok(number_value(
+            number.round_dp_with_strategy(places, strategy),
+            span,
+        ))
+    })
+}
+
+fn rounding_strategy(arg: &SpannedValue) -> Result<RoundingStrategy, EvalError> {
+    let strategy = match args::string(arg)? {
+        "half_even" => RoundingStrategy::MidpointNearestEven,
+        "half_away_from_zero" => RoundingStrategy::MidpointAwayFromZero,
+        "half_toward_zero" => RoundingStrategy::MidpointTowardZero,
+        "toward_zero" => RoundingStrategy::ToZero,
+        "away_from_zero" => RoundingStrategy::AwayFromZero,
+        "floor" => RoundingStrategy::ToNegativeInfinity,
+        "ceil" => RoundingStrategy::ToPositiveInfinity,
+        other => {
+            return Err(args::invalid_argument(
+                arg,
+                format!("Unknown rounding strategy: {}", other),
+            ))
+        }
+    };
+    Ok(strategy)
+}
+
+/// Raise a number to a power. An integer exponent is exact, while any other
+/// exponent is approximate, to 24 significant digits. A result too small to
+/// represent rounds to `0`.
+pub fn pow() -> Function {
+    native_function("pow", &["base", "exponent"], |span, args| {
+        let base = args::number(&args[0])?;
+        let exponent = args::number(&args[1])?;
+        let integer = exponent.fract().is_zero();
+        if base.is_zero() && exponent.is_sign_negative() {
+            return Err(EvalError::Arithmetic {
+                span,
+                message: "Division by zero".into(),
+            });
+        }
+        if base.is_sign_negative() && !integer {
+            return Err(args::invalid_argument(
+                &args[1],
+                "Fractional exponent of a negative number",
+            ));
+        }
+        // these never overflow, however large the exponent
+        if base.is_zero() {
+            let result = if exponent.is_zero() { 1 } else { 0 };
+            return Ok(number_value(Decimal::from(result), span));
+        }
+        if base.abs() == Decimal::ONE {
+            let even = (*exponent / Decimal::TWO).fract().is_zero();
+            let result = if base.is_sign_negative() && !even {
+                -1
+            } else {
+                1
+            };
+            return Ok(number_value(Decimal::from(result), span));
+        }
+        let result = if integer {
+            exponent
+                .to_i64()
+                .and_then(|exponent| base.checked_powi(exponent))
+        } else {
+            fractional_pow(*base, *exponent)
+        };
+        match result {
+            Some(result) => Ok(number_value(result.normalize(), span)),
+            // the magnitude of the result is below one, so it underflowed
+            None if (base.abs() < Decimal::ONE) != exponent.is_sign_negative() => {
+                Ok(number_value(Decimal::ZERO, span))
+            }
+            None => Err(too_large(span)),
+        }
+    })
+}
+
+/// A positive base to a fractional exponent, as the exact power of the
+/// integer part times `exp(fraction * ln(base))`.
+///
+/// `rust_decimal`'s own `powd` and `exp` lose precision (`powd(1e-28, 0.5)`
+/// is 5% off), so this sums the series for `exp` of the fraction itself.
+fn fractional_pow(base: Decimal, exponent: Decimal) -> Option<Decimal> {
+    let integer = base.checked_powi(exponent.trunc().to_i64()?)?;
+    // `ln` is only precise for numbers above one
+    let ln = if base < Decimal::ONE {
+        -(Decimal::ONE / base).ln()
+    } else {
+        base.ln()
+    };
+    let fraction = exp(exponent.fract() * ln)?;
+    integer.checked_mul(fraction)?.round_sf(24)
+}
+
+/// `e` to a power, as `e` to the integer part times the series for the rest.
+fn exp(power: Decimal) -> Option<Decimal> {
+    let integer = Decimal::E.checked_powi(power.trunc().to_i64()?)?;
+    let fraction = power.fract();
+    let mut sum = Decimal::ONE;
+    let mut term = Decimal::ONE;
+    let mut index = Decimal::ONE;
+    while !term.is_zero() {
+        term = term * fraction / index;
+        sum += term;
+        index += Decimal::ONE;
+    }
+    integer.checked_mul(sum)
+}
+
+pub fn sqrt() -> Function {
+    native_function("sqrt", &["number"], |span, args| {
+        let number = args::number(&args[0])?;
+        match number.sqrt() {
+            Some(result) => Ok(number_value(result.normalize(), span)),
+            None => Err(args::invalid_argument(
+                &args[0],
+                "Square root of a negative number",
+            )),
+        }
+    })
+}
+
+/// Restrict a number to be between a minimum and maximum, inclusive.
+pub fn clamp() -> Function {
+    native_function("clamp", &["number", "min", "max"], |span, args| {
+        let number = args::number(&args[0])?;
+        let min = args::number(&args[1])?;
+        let max = args::number(&args[2])?;
+        if min > max {
+            return Err(args::invalid_argument(
+                &args[1],
+                format!("Minimum {} is greater than maximum {}", min, max),
+            ));
+        }
+        Ok(number_value(number.clamp(min, max), span))
+    })
+}
+
+/// Add a list of numbers. The sum of an empty list is `0`.
+pub fn sum() -> Function {
+    native_function("sum", &["list"], |span, args| {
+        let total = numbers(&args[0])?
+            .into_iter()
+            .try_fold(Number::from(0), Number::checked_add)
+            .ok_or_else(|| too_large(span.clone()))?;
+        Ok(number_value(total, span))
+    })
+}
+
+/// Multiply a list of numbers. The product of an empty list is `1`.
+pub fn product() -> Function {
+    native_function("product", &["list"], |span, args| {
+        let total = numbers(&args[0])?
+            .into_iter()
+            .try_fold(Number::from(1), Number::checked_mul)
+            .ok_or_else(|| too_large(span.clone()))?;
+        Ok(number_value(total, span))
+    })
+}
+
+/// Convert a number to an integer by dropping any fraction, or parse a string
+/// of an integer.
+pub fn to_int() -> Function {
+    native_function("to_int", &["arg"], |span, args| {
+        let arg = &args[0];
+        let number = match arg.inner() {
+            Value::Number(number) => number.trunc(),
+            Value::String(string) => match Decimal::from_str(string) {
+                Ok(number) if number.fract().is_zero() => number.trunc(),
+                _ => {
+                    return Err(args::invalid_argument(
+                        arg,
+                        format!("Not an integer: {:?}", string),
+                    ))
+                }
+            },
+            _ => return Err(args::type_error(arg, "number | string")),
+        };
+        Ok(number_value(number, span))
+    })
+}
+
+/// Parse a string of a number, in decimal (`"1.5"`) or scientific (`"1.5e3"`)
+/// notation.
+pub fn parse_number() -> Function {
+    native_function("parse_number", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        let number = Decimal::from_str(string)
+            .or_else(|_| Decimal::from_scientific(string))
+            .map_err(|_| args::invalid_argument(&args[0], format!("Not a number: {:?}", string)))?;
+        Ok(number_value(number.normalize(), span))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{arithmetic, assert_err, assert_true, invalid_argument, type_error};
+
+    #[test]
+    fn min_and_max() {
+        assert_true("min([3, -1.5, 2]) == -1.5");
+        assert_true("max([3, -1.5, 2]) == 3");
+        assert_true("min([]) == null && max([]) == null");
+    }
+
+    #[test]
+    fn abs_floor_ceil() {
+        assert_true("abs(-2.5) == 2.5 && abs(2) == 2");
+        assert_true("floor(2.7) == 2 && floor(-2.2) == -3");
+        assert_true("ceil(2.2) == 3 && ceil(-2.7) == -2");
+    }
+
+    #[test]
+    fn round() {
+        assert_true("round(2.5) == 3 && round(-2.5) == -3");
+        assert_true("round(3.14159, 2) == 3.14");
+        assert_true(r#"round(2.5, 0, "half_even") == 2 && round(3.5, 0, "half_even") == 4"#);
+        assert_true(r#"round(2.5, 0, "half_toward_zero") == 2"#);
+        assert_true(r#"round(2.9, 0, "toward_zero") == 2"#);
+        assert_true(r#"round(2.1, 0, "away_from_zero") == 3"#);
+        assert_true(r#"round(-2.1, 0, "floor") == -3 && round(-2.9, 0, "ceil") == -2"#);
+        assert_true("round(1.5, 40) == 1.5");
+    }
+
+    #[test]
+    fn pow_and_sqrt() {
+        assert_true("pow(2, 10) == 1024");
+        assert_true("pow(2, -2) == 0.25");
+        assert_true("pow(1.5, 2) == 2.25");
+        assert_true("pow(4, 0.5) == 2 && pow(8, 1 / 3) == 2");
+        assert_true("pow(2, 0.5) == 1.41421356237309504880169");
+        assert_true("pow(0.0000000000000000000000000001, 0.5) == 0.00000000000001");
+        assert_true("pow(1.5, 2.5) == 2.75567596063107536047194");
+        assert_true("pow(10, -0.5) == 0.316227766016837933199889");
+        // results too small to represent round to zero
+        assert_true("pow(2, -100) == 0 && pow(0.1, 100) == 0 && pow(2, -100.5) == 0");
+        assert_true("pow(0, 0.5) == 0 && pow(0, 1.5) == 0 && pow(0, 0) == 1");
+        assert_true("pow(1, 100000000000000000000) == 1 && pow(1, 0.5) == 1");
+        assert_true("pow(-1, 100000000000000000000) == 1 && pow(-1, 100000000000000000001) == -1");
+        assert_true("sqrt(16) == 4");
+        assert_true("sqrt(2) == 1.4142135623730950488016887242");
+    }
+
+    #[test]
+    fn clamp() {
+        assert_true("clamp(5, 0, 3) == 3 && clamp(-1, 0, 3) == 0 && clamp(2, 0, 3) == 2");
+    }
+
+    #[test]
+    fn sum_and_product() {
+        assert_true("sum([1, 2, 3.5]) == 6.5 && sum([]) == 0");
+        assert_true("product([2, 3, 0.5]) == 3 && product([]) == 1");
+    }
+
+    #[test]
+    fn conversions() {
+        assert_true("to_int(2.9) == 2 && to_int(-2.9) == -2");
+        assert_true(r#"to_int("42") == 42 && to_int("-7") == -7"#);
+        assert_true(r#"parse_number("1.5") == 1.5"#);
+        assert_true(r#"parse_number("1.5e3") == 1500"#);
+        assert_true(r#"parse_number("-2E-2") == -0.02"#);
+    }
+
+    #[test]
+    fn errors() {
+        assert_err(r#"round(1.5, 0, "sideways")"#, invalid_argument);
+        assert_err("sqrt(-1)", invalid_argument);
+        assert_err("pow(-8, 0.5)", invalid_argument);
+        assert_err("clamp(1, 3, 0)", invalid_argument);
+        assert_err(r#"to_int("4.5")"#, invalid_argument);
+        assert_err(r#"to_int("four")"#, invalid_argument);
+        assert_err(r#"parse_number("1.2.3")"#, invalid_argument);
+
+        assert_err("pow(0, -1)", arithmetic);
+        assert_err("pow(0, -0.5)", arithmetic);
+        assert_err("pow(10, 100)", arithmetic);
+        assert_err("sum([79228162514264337593543950335, 1])", arithmetic);
+        assert_err("product([79228162514264337593543950335, 2])", arithmetic);
+
+        assert_err(r#"abs("1")"#, type_error);
+        assert_err(r#"min([1, "2"])"#, type_error);
+        assert_err("to_int(null)", type_error);
+    }
+}
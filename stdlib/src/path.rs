@@ -0,0 +1,364 @@
+//! Functions on host and target paths.
+//!
+//! Functions which return a path keep the kind of path they're given. Host
+//! paths use the semantics of the local machine, and target paths use
+//! [`typed_path`]'s unix semantics. All functions are lexical: they never
+//! touch the filesystem.
+
+use std::path::{Component, Path, PathBuf};
+
+use rimu_meta::{Span, Spanned};
+use rimu_value::{EvalError, Function, SerdeValueObject, SpannedValue, Value};
+use typed_path::{Utf8TypedPath, Utf8TypedPathBuf};
+
+use crate::{args, native_function, NativeResult};
+
+pub(crate) fn insert(lib: &mut SerdeValueObject) {
+    lib.insert("parent".into(), parent().into());
+    lib.insert("file_name".into(), file_name().into());
+    lib.insert("file_stem".into(), file_stem().into());
+    lib.insert("extension".into(), extension().into());
+    lib.insert("with_extension".into(), with_extension().into());
+    lib.insert("components".into(), components().into());
+    lib.insert("normalize".into(), normalize().into());
+    lib.insert("is_absolute".into(), is_absolute().into());
+    lib.insert("relative_to".into(), relative_to().into());
+}
+
+enum PathArg<'a> {
+    Host(&'a Path),
+    Target(Utf8TypedPath<'a>),
+}
+
+fn path(arg: &SpannedValue) -> Result<PathArg<'_>, EvalError> {
+    match arg.inner() {
+        Value::HostPath(path) => Ok(PathArg::Host(path)),
+        Value::TargetPath(path) => Ok(PathArg::Target(path.to_path())),
+        _ => Err(args::type_error(arg, "host-path | target-path")),
+    }
+}
+
+/// A string, or `null` if there is none.
+fn optional_string(string: Option<impl Into<String>>, span: Span) -> SpannedValue {
+    let value = match string {
+        Some(string) => Value::String(string.into()),
+        None => Value::Null,
+    };
+    Spanned::new(value, span)
+}
+
+/// The path without its last component, or `null` for a root or empty path.
+pub fn parent() -> Function {
+    native_function("parent", &["path"], |span, args| {
+        let value = match path(&args[0])? {
+            PathArg::Host(path) => path.parent().map(|p| Value::HostPath(p.to_path_buf())),
+            PathArg::Target(path) => path.parent().map(|p| Value::TargetPath(p.to_path_buf())),
+        };
+        Ok(Spanned::new(value.unwrap_or(Value::Null), span))
+    })
+}
+
+/// The last component of a path, or `null` if there is none.
+pub fn file_name() -> Function {
+    native_function("file_name", &["path"], |span, args| {
+        let name = match path(&args[0])? {
+            PathArg::Host(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+            PathArg::Target(path) => path.file_name().map(String::from),
+        };
+        Ok(optional_string(name, span))
+    })
+}
+
+/// The file name without its extension, or `null` if there is none.
+pub fn file_stem() -> Function {
+    native_function("file_stem", &["path"], |span, args| {
+        let stem = match path(&args[0])? {
+            PathArg::Host(path) => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned()),
+            PathArg::Target(path) => path.file_stem().map(String::from),
+        };
+        Ok(optional_string(stem, span))
+    })
+}
+
+/// The extension of the file name, without the `.`, or `null` if there is
+/// none.
+pub fn extension() -> Function {
+    native_function("extension", &["path"], |span, args| {
+        let extension = match path(&args[0])? {
+            PathArg::Host(path) => path
+                .extension()
+                .map(|extension| extension.to_string_lossy().into_owned()),
+            PathArg::Target(path) => path.extension().map(String::from),
+        };
+        Ok(optional_string(extension, span))
+    })
+}
+
+/// The path with the extension of its file name replaced, or removed if the
+/// extension is `""`.
+pub fn with_extension() -> Function {
+    native_function("with_extension", &["path", "extension"], |span, args| {
+        let extension = args::string(&args[1])?;
+        let value = match path(&args[0])? {
+            PathArg::Host(path) => Value::HostPath(path.with_extension(extension)),
+            PathArg::Target(path) => Value::TargetPath(target_with_extension(path, extension)),
+        };
+        Ok(Spanned::new(value, span))
+    })
+}
+
+/// `typed_path` sets the extension of an empty last component when the path
+/// ends in a separator, so rebuild the path from its file name first, as the
+/// host path does.
+fn target_with_extension(path: Utf8TypedPath, extension: &str) -> Utf8TypedPathBuf {
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(file_name)) => parent.join(file_name).with_extension(extension),
+        _ => path.with_extension(extension),
+    }
+}
+
+fn host_components(path: &Path) -> Vec<String> {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect()
+}
+
+fn target_components(path: Utf8TypedPath) -> Vec<String> {
+    path.components()
+        .map(|component| component.as_str().to_string())
+        .collect()
+}
+
+/// The components of a path as a list of strings, starting with `"/"` for
+/// an absolute path.
+pub fn components() -> Function {
+    native_function("components", &["path"], |span, args| {
+        let components = match path(&args[0])? {
+            PathArg::Host(path) => host_components(path),
+            PathArg::Target(path) => target_components(path),
+        };
+        let list = components
+            .into_iter()
+            .map(|component| Spanned::new(Value::String(component), span.clone()))
+            .collect();
+        Ok(Spanned::new(Value::List(list), span))
+    })
+}
+
+/// Lexically resolve `.` and `..` in a host path. A `..` at the root stays at
+/// the root, while a leading `..` in a relative path is kept.
+fn normalize_host(path: &Path) -> PathBuf {
+    let mut normal = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normal.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normal.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normal.push(".."),
+            },
+            component => normal.push(component),
+        }
+    }
+    if normal.as_os_str().is_empty() {
+        normal.push(".");
+    }
+    normal
+}
+
+/// Lexically resolve `.` and `..` in a path, without touching the
+/// filesystem.
+pub fn normalize() -> Function {
+    native_function("normalize", &["path"], |span, args| {
+        let value = match path(&args[0])? {
+            PathArg::Host(path) => Value::HostPath(normalize_host(path)),
+            PathArg::Target(path) => Value::TargetPath(path.normalize()),
+        };
+        Ok(Spanned::new(value, span))
+    })
+}
+
+pub fn is_absolute() -> Function {
+    native_function("is_absolute", &["path"], |span, args| {
+        let absolute = match path(&args[0])? {
+            PathArg::Host(path) => path.is_absolute(),
+            PathArg::Target(path) => path.is_absolute(),
+        };
+        Ok(Spanned::new(Value::Boolean(absolute), span))
+    })
+}
+
+/// The relative path from a base to a path, as a string, after normalizing
+/// both. Goes up from the base with `..` as needed. Both must be the same
+/// kind of path, and both absolute or both relative.
+pub fn relative_to() -> Function {
+    native_function("relative_to", &["path", "base"], |span, args| {
+        let (components, base, separator) = match (path(&args[0])?, path(&args[1])?) {
+            (PathArg::Host(path), PathArg::Host(base)) => {
+                if path.is_absolute() != base.is_absolute() {
+                    return Err(mixed_absolute(&args[1]));
+                }
+                (
+                    host_components(&normalize_host(path)),
+                    host_components(&normalize_host(base)),
+                    std::path::MAIN_SEPARATOR_STR,
+                )
+            }
+            (PathArg::Target(path), PathArg::Target(base)) => {
+                if path.is_absolute() != base.is_absolute() {
+                    return Err(mixed_absolute(&args[1]));
+                }
+                (
+                    target_components(path.normalize().to_path()),
+                    target_components(base.normalize().to_path()),
+                    "/",
+                )
+            }
+            (PathArg::Host(_), _) => return Err(args::type_error(&args[1], "host-path")),
+            (PathArg::Target(_), _) => return Err(args::type_error(&args[1], "target-path")),
+        };
+        // a normalized path is only `.` when it's empty
+        let components: Vec<String> = components.into_iter().filter(|c| c != ".").collect();
+        let base: Vec<String> = base.into_iter().filter(|c| c != ".").collect();
+        let common = components
+            .iter()
+            .zip(base.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let relative: Vec<&str> = std::iter::repeat_n("..", base.len() - common)
+            .chain(components[common..].iter().map(String::as_str))
+            .collect();
+        let relative = if relative.is_empty() {
+            ".".to_string()
+        } else {
+            relative.join(separator)
+        };
+        Ok(Spanned::new(Value::String(relative), span))
+    })
+}
+
+fn mixed_absolute(base: &SpannedValue) -> EvalError {
+    EvalError::InvalidArgument {
+        span: base.span(),
+        message: "Path and base must both be absolute or both be relative".into(),
+    }
+}
+
+/// Extend a path with each segment in turn, keeping its kind. As with `+`,
+/// leading `/`s on a segment are stripped so it always extends the path.
+pub(crate) fn join(span: Span, args: &[SpannedValue]) -> NativeResult {
+    let segments = args[1..]
+        .iter()
+        .map(|arg| args::string(arg).map(|segment| segment.trim_start_matches('/')))
+        .collect::<Result<Vec<&str>, _>>()?;
+    let value = match path(&args[0])? {
+        PathArg::Host(path) => Value::HostPath(
+            segments
+                .iter()
+                .fold(path.to_path_buf(), |path, segment| path.join(segment)),
+        ),
+        PathArg::Target(path) => Value::TargetPath(
+            segments
+                .iter()
+                .fold(path.to_path_buf(), |path, segment| path.join(segment)),
+        ),
+    };
+    Ok(Spanned::new(value, span))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{assert_err, assert_true, type_error};
+
+    #[test]
+    fn parts() {
+        assert_true(r#"parent(target_path("/etc/nginx/nginx.conf")) == target_path("/etc/nginx")"#);
+        assert_true(r#"parent(target_path("/")) == null"#);
+        assert_true(r#"file_name(target_path("/etc/nginx.conf")) == "nginx.conf""#);
+        assert_true(r#"file_stem(target_path("/etc/nginx.conf")) == "nginx""#);
+        assert_true(r#"extension(target_path("/etc/nginx.conf")) == "conf""#);
+        assert_true(r#"extension(target_path("/etc/hosts")) == null"#);
+        assert_true(r#"file_name(host_path("./config.yaml")) == "config.yaml""#);
+        assert_true(r#"extension(host_path("./archive.tar.gz")) == "gz""#);
+    }
+
+    #[test]
+    fn with_extension() {
+        assert_true(
+            r#"with_extension(target_path("/etc/app.yaml"), "json") == target_path("/etc/app.json")"#,
+        );
+        assert_true(
+            r#"with_extension(target_path("/etc/app.yaml"), "") == target_path("/etc/app")"#,
+        );
+        assert_true(
+            r#"with_extension(host_path("./app.yaml"), "json") == host_path("./app.json")"#,
+        );
+        assert_true(r#"with_extension(target_path("/etc/"), "x") == target_path("/etc.x")"#);
+        assert_true(r#"with_extension(target_path("/etc//"), "x") == target_path("/etc.x")"#);
+        assert_true(r#"with_extension(host_path("./etc/"), "x") == host_path("./etc.x")"#);
+    }
+
+    #[test]
+    fn components() {
+        assert_true(
+            r#"components(target_path("/etc/nginx/nginx.conf")) == ["/", "etc", "nginx", "nginx.conf"]"#,
+        );
+        assert_true(r#"components(host_path("./a/b")) == ["/", "tmp", "a", "b"]"#);
+    }
+
+    #[test]
+    fn normalize() {
+        assert_true(r#"to_string(normalize(host_path("./x"))) == "/tmp/x""#);
+        assert_true(r#"to_string(normalize(host_path("./a/../b/./c"))) == "/tmp/b/c""#);
+        assert_true(r#"to_string(normalize(host_path("../../../.."))) == "/""#);
+        assert_true(
+            r#"normalize(target_path("/etc/./nginx/../hosts")) == target_path("/etc/hosts")"#,
+        );
+        assert_true(r#"normalize(target_path("/../etc")) == target_path("/etc")"#);
+    }
+
+    #[test]
+    fn is_absolute() {
+        assert_true(r#"is_absolute(target_path("/etc")) && is_absolute(host_path("./x"))"#);
+    }
+
+    #[test]
+    fn relative_to() {
+        assert_true(
+            r#"relative_to(target_path("/etc/nginx/nginx.conf"), target_path("/etc")) == "nginx/nginx.conf""#,
+        );
+        assert_true(
+            r#"relative_to(target_path("/etc/hosts"), target_path("/var/lib")) == "../../etc/hosts""#,
+        );
+        assert_true(r#"relative_to(target_path("/etc"), target_path("/etc/")) == ".""#);
+        assert_true(r#"relative_to(host_path("./a/b"), host_path("./c/../a")) == "b""#);
+        assert_true(r#"relative_to(parent(host_path("./a")), host_path("./a")) == "..""#);
+    }
+
+    #[test]
+    fn join() {
+        assert_true(
+            r#"join(target_path("/etc"), "nginx", "/conf.d") == target_path("/etc/nginx/conf.d")"#,
+        );
+        assert_true(r#"join(host_path("./a"), "b") == host_path("./a") + "b""#);
+        assert_true(r#"join(["a", "b"], "/") == "a/b""#);
+    }
+
+    #[test]
+    fn errors() {
+        for code in [
+            r#"parent("/etc")"#,
+            r#"relative_to(target_path("/etc"), host_path("./etc"))"#,
+            r#"join(target_path("/etc"), 1)"#,
+            r#"join(1, "/")"#,
+        ] {
+            assert_err(code, type_error);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Functions on regular expression patterns, using the syntax of the
+//! [`regex`](https://docs.rs/regex) crate.
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use regex::Regex;
+use rimu_meta::Spanned;
+use rimu_value::{EvalError, Function, SerdeValueObject, SpannedValue, Value, ValueObject};
+
+use crate::{args, native_function};
+
+/// Compiled patterns, so a pattern used for each item of a list is only
+/// compiled once. Each stdlib has its own, shared by its regex functions, so
+/// it's dropped along with the environment of an evaluation.
+type Cache = Rc<RefCell<HashMap<String, Regex>>>;
+
+pub(crate) fn insert(lib: &mut SerdeValueObject) {
+    let cache = Cache::default();
+    lib.insert("regex_match".into(), regex_match(&cache).into());
+    lib.insert("regex_find_all".into(), regex_find_all(&cache).into());
+    lib.insert("regex_captures".into(), regex_captures(&cache).into());
+    lib.insert("regex_replace".into(), regex_replace(&cache).into());
+}
+
+/// The most compiled patterns to keep. When full, the cache is cleared rather
+/// than tracking usage.
+const CACHE_CAPACITY: usize = 64;
+
+/// The longest pattern to keep, so the cache can't grow large however many
+/// or long patterns an evaluation uses.
+const CACHE_MAX_PATTERN_LENGTH: usize = 1024;
+
+fn compile(cache: &Cache, arg: &SpannedValue) -> Result<Regex, EvalError> {
+    let pattern = args::string(arg)?;
+    if let Some(regex) = cache.borrow().get(pattern) {
+        return Ok(regex.clone());
+    }
+    let regex = Regex::new(pattern).map_err(|error| EvalError::InvalidArgument {
+        span: arg.span(),
+        message: format!("Invalid regex: {}", error),
+    })?;
+    if pattern.len() > CACHE_MAX_PATTERN_LENGTH {
+        return Ok(regex);
+    }
+    let mut cache = cache.borrow_mut();
+    if cache.len() >= CACHE_CAPACITY {
+        cache.clear();
+    }
+    cache.insert(pattern.to_string(), regex.clone());
+    Ok(regex)
+}
+
+/// Whether a pattern matches anywhere in a string.
+pub fn regex_match(cache: &Cache) -> Function {
+    let cache = cache.clone();
+    native_function("regex_match", &["string", "pattern"], move |span, args| {
+        let string = args::string(&args[0])?;
+        let regex = compile(&cache, &args[1])?;
+        Ok(Spanned::new(Value::Boolean(regex.is_match(string)), span))
+    })
+}
+
+/// Every match of a pattern in a string, as a list of strings.
+pub fn regex_find_all(cache: &Cache) -> Function {
+    let cache = cache.clone();
+    native_function(
+        "regex_find_all",
+        &["string", "pattern"],
+        move |span, args| {
+            let string = args::string(&args[0])?;
+            let regex = compile(&cache, &args[1])?;
+            let list = regex
+                .find_iter(string)
+                .map(|found| Spanned::new(Value::String(found.as_str().into()), span.clone()))
+                .collect();
+            Ok(Spanned::new(Value::List(list), span))
+        },
+    )
+}
+
+/// The named groups of the first match of a pattern in a string, as an
+/// object. A group which didn't match is `null`, and if the pattern doesn't
+/// match at all the result is `null`.
+pub fn regex_captures(cache: &Cache) -> Function {
+    let cache = cache.clone();
+    native_function(
+        "regex_captures",
+        &["string", "pattern"],
+        move |span, args| {
+            let string = args::string(&args[0])?;
+            let regex = compile(&cache, &args[1])?;
+            let Some(captures) = regex.captures(string) else {
+                return Ok(Spanned::new(Value::Null, span));
+            };
+            let object: ValueObject = regex
+                .capture_names()
+                .flatten()
+                .map(|name| {
+                    let value = match captures.name(name) {
+                        Some(group) => Value::String(group.as_str().into()),
+                        None => Value::Null,
+                    };
+                    (name.to_string(), Spanned::new(value, span.clone()))
+                })
+                .collect();
+            Ok(Spanned::new(Value::Object(object), span))
+        },
+    )
+}
+
+/// Replace every match of a pattern in a string. The replacement may refer to
+/// groups with `$1` or `${name}`.
+pub fn regex_replace(cache: &Cache) -> Function {
+    let cache = cache.clone();
+    native_function(
+        "regex_replace",
+        &["string", "pattern", "replacement"],
+        move |span, args| {
+            let string = args::string(&args[0])?;
+            let regex = compile(&cache, &args[1])?;
+            let replacement = args::string(&args[2])?;
+            let value = regex.replace_all(string, replacement).into_owned();
+            Ok(Spanned::new(Value::String(value), span))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rimu_value::EvalError;
+
+    use crate::tests::{assert_err, assert_true, eval_with_stdlib, type_error};
+
+    #[test]
+    fn match_and_find_all() {
+        assert_true(r#"regex_match("web-01", "^[a-z]+-\\d+$")"#);
+        assert_true(r#"!regex_match("web_01", "^[a-z]+-\\d+$")"#);
+        assert_true(r#"regex_find_all("a1 b22 c333", "\\d+") == ["1", "22", "333"]"#);
+        assert_true(r#"regex_find_all("abc", "\\d+") == []"#);
+    }
+
+    #[test]
+    fn captures() {
+        assert_true(
+            r#"regex_captures("nginx:1.25", "^(?P<image>[^:]+)(:(?P<tag>.+))?$") == { image: "nginx", tag: "1.25" }"#,
+        );
+        assert_true(
+            r#"regex_captures("nginx", "^(?P<image>[^:]+)(:(?P<tag>.+))?$") == { image: "nginx", tag: null }"#,
+        );
+        assert_true(r#"regex_captures("abc", "(?P<n>\\d+)") == null"#);
+    }
+
+    #[test]
+    fn replace() {
+        assert_true(
+            r#"regex_replace(lower("Web Server 01"), "[^a-z0-9]+", "-") == "web-server-01""#,
+        );
+        assert_true(
+            r#"regex_replace("a=1, b=2", "(?P<key>\\w)=(?P<value>\\d)", "${value}=${key}") == "1=a, 2=b""#,
+        );
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let actual = eval_with_stdlib(r#"regex_match("abc", "(")"#);
+        let Err(EvalError::InvalidArgument { span, .. }) = actual else {
+            panic!("expected invalid argument, got: {:?}", actual);
+        };
+        // points at the pattern argument
+        assert_eq!((span.start(), span.end()), (19, 22));
+
+        assert_err(r#"regex_match("abc", 1)"#, type_error);
+    }
+}
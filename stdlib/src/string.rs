@@ -0,0 +1,301 @@
+//! Functions on strings.
+//!
+//! Lengths and positions count Unicode characters (scalar values), not bytes.
+
+use rimu_meta::Spanned;
+use rimu_value::{EvalError, Function, SerdeValueObject, SpannedValue, Value};
+
+use crate::{args, native_function, path};
+
+pub(crate) fn insert(lib: &mut SerdeValueObject) {
+    lib.insert("split".into(), split().into());
+    lib.insert("join".into(), join().into());
+    lib.insert("trim".into(), trim().into());
+    lib.insert("trim_start".into(), trim_start().into());
+    lib.insert("trim_end".into(), trim_end().into());
+    lib.insert("upper".into(), upper().into());
+    lib.insert("lower".into(), lower().into());
+    lib.insert("replace".into(), replace().into());
+    lib.insert("starts_with".into(), starts_with().into());
+    lib.insert("ends_with".into(), ends_with().into());
+    lib.insert("contains".into(), contains().into());
+    lib.insert("pad_start".into(), pad_start().into());
+    lib.insert("pad_end".into(), pad_end().into());
+    lib.insert("repeat".into(), repeat().into());
+    lib.insert("chars".into(), chars().into());
+}
+
+/// The most characters `pad_start`, `pad_end` and `repeat` may build, so a
+/// mistaken length is an error rather than exhausting memory.
+const MAX_LENGTH: usize = 1 << 24;
+
+fn too_long(arg: &SpannedValue) -> EvalError {
+    EvalError::InvalidArgument {
+        span: arg.span(),
+        message: format!("Result would be longer than {} characters", MAX_LENGTH),
+    }
+}
+
+/// Split a string into a list of strings on each separator. An empty
+/// separator splits the string into characters.
+pub fn split() -> Function {
+    native_function("split", &["string", "separator"], |span, args| {
+        let string = args::string(&args[0])?;
+        let separator = args::string(&args[1])?;
+        let list = if separator.is_empty() {
+            string
+                .chars()
+                .map(|c| args::string_value(c, span.clone()))
+                .collect()
+        } else {
+            string
+                .split(separator)
+                .map(|part| args::string_value(part, span.clone()))
+                .collect()
+        };
+        Ok(Spanned::new(Value::List(list), span))
+    })
+}
+
+/// Join a list of strings into a string, with a separator between each. Or
+/// given a path, extend it with each following string.
+pub fn join() -> Function {
+    native_function("join", &["list", "separator"], |span, args| {
+        let list = match args[0].inner() {
+            Value::HostPath(_) | Value::TargetPath(_) => return path::join(span, args),
+            Value::List(list) => list,
+            _ => return Err(args::type_error(&args[0], "list | host-path | target-path")),
+        };
+        let separator = args::string(&args[1])?;
+        let strings = list
+            .iter()
+            .map(args::string)
+            .collect::<Result<Vec<&str>, _>>()?;
+        Ok(args::string_value(strings.join(separator), span))
+    })
+}
+
+pub fn trim() -> Function {
+    native_function("trim", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        Ok(args::string_value(string.trim(), span))
+    })
+}
+
+pub fn trim_start() -> Function {
+    native_function("trim_start", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        Ok(args::string_value(string.trim_start(), span))
+    })
+}
+
+pub fn trim_end() -> Function {
+    native_function("trim_end", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        Ok(args::string_value(string.trim_end(), span))
+    })
+}
+
+pub fn upper() -> Function {
+    native_function("upper", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        Ok(args::string_value(string.to_uppercase(), span))
+    })
+}
+
+pub fn lower() -> Function {
+    native_function("lower", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        Ok(args::string_value(string.to_lowercase(), span))
+    })
+}
+
+/// Replace every occurrence of a pattern in a string.
+pub fn replace() -> Function {
+    native_function("replace", &["string", "from", "to"], |span, args| {
+        let string = args::string(&args[0])?;
+        let from = args::string(&args[1])?;
+        let to = args::string(&args[2])?;
+        Ok(args::string_value(string.replace(from, to), span))
+    })
+}
+
+pub fn starts_with() -> Function {
+    native_function("starts_with", &["string", "prefix"], |span, args| {
+        let string = args::string(&args[0])?;
+        let prefix = args::string(&args[1])?;
+        Ok(Spanned::new(
+            Value::Boolean(string.starts_with(prefix)),
+            span,
+        ))
+    })
+}
+
+pub fn ends_with() -> Function {
+    native_function("ends_with", &["string", "suffix"], |span, args| {
+        let string = args::string(&args[0])?;
+        let suffix = args::string(&args[1])?;
+        Ok(Spanned::new(Value::Boolean(string.ends_with(suffix)), span))
+    })
+}
+
+pub fn contains() -> Function {
+    native_function("contains", &["string", "substring"], |span, args| {
+        let string = args::string(&args[0])?;
+        let substring = args::string(&args[1])?;
+        Ok(Spanned::new(
+            Value::Boolean(string.contains(substring)),
+            span,
+        ))
+    })
+}
+
+/// Pad the start of a string to a length, with an optional fill string
+/// (default `" "`).
+pub fn pad_start() -> Function {
+    native_function("pad_start", &["string", "length"], |span, args| {
+        let (string, padding) = pad(args)?;
+        Ok(args::string_value(padding + string, span))
+    })
+}
+
+/// Pad the end of a string to a length, with an optional fill string
+/// (default `" "`).
+pub fn pad_end() -> Function {
+    native_function("pad_end", &["string", "length"], |span, args| {
+        let (string, padding) = pad(args)?;
+        Ok(args::string_value(string.to_string() + &padding, span))
+    })
+}
+
+/// The string to pad, and the padding needed to reach the length.
+fn pad(args: &[SpannedValue]) -> Result<(&str, String), EvalError> {
+    let string = args::string(&args[0])?;
+    let length = args::count(&args[1])?;
+    if length > MAX_LENGTH {
+        return Err(too_long(&args[1]));
+    }
+    let fill = match args.get(2) {
+        Some(fill) => match args::string(fill)? {
+            "" => return Err(args::invalid_argument(fill, "Fill must not be empty")),
+            string => string,
+        },
+        None => " ",
+    };
+    let padding = fill
+        .chars()
+        .cycle()
+        .take(length.saturating_sub(string.chars().count()))
+        .collect();
+    Ok((string, padding))
+}
+
+pub fn repeat() -> Function {
+    native_function("repeat", &["string", "count"], |span, args| {
+        let string = args::string(&args[0])?;
+        let count = args::count(&args[1])?;
+        match string.chars().count().checked_mul(count) {
+            Some(length) if length <= MAX_LENGTH => {}
+            _ => return Err(too_long(&args[1])),
+        }
+        Ok(args::string_value(string.repeat(count), span))
+    })
+}
+
+/// Split a string into a list of its characters.
+pub fn chars() -> Function {
+    native_function("chars", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        let list = string
+            .chars()
+            .map(|c| args::string_value(c, span.clone()))
+            .collect();
+        Ok(Spanned::new(Value::List(list), span))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rimu_value::EvalError;
+
+    use crate::tests::{assert_err, assert_true, eval_with_stdlib, invalid_argument, type_error};
+
+    #[test]
+    fn split_and_join() {
+        assert_true(r#"split("a,b,,c", ",") == ["a", "b", "", "c"]"#);
+        assert_true(r#"split("héllo", "") == ["h", "é", "l", "l", "o"]"#);
+        assert_true(r#"join(["a", "b", "c"], ", ") == "a, b, c""#);
+        assert_true(r#"join([], ", ") == """#);
+    }
+
+    #[test]
+    fn trim_and_case() {
+        assert_true(r#"trim("  a b  ") == "a b""#);
+        assert_true(r#"trim_start("  a  ") == "a  ""#);
+        assert_true(r#"trim_end("  a  ") == "  a""#);
+        assert_true(r#"upper("straße") == "STRASSE""#);
+        assert_true(r#"lower("ÀB") == "àb""#);
+    }
+
+    #[test]
+    fn search_and_replace() {
+        assert_true(r#"replace("a-b-c", "-", "_") == "a_b_c""#);
+        assert_true(r#"starts_with("hello", "he") && !starts_with("hello", "lo")"#);
+        assert_true(r#"ends_with("hello", "lo") && !ends_with("hello", "he")"#);
+        assert_true(r#"contains("hello", "ell") && !contains("hello", "x")"#);
+    }
+
+    #[test]
+    fn pad_and_repeat() {
+        assert_true(r#"pad_start("7", 3, "0") == "007""#);
+        assert_true(r#"pad_start("é", 3) == "  é""#);
+        assert_true(r#"pad_end("ab", 5, "xy") == "abxyx""#);
+        assert_true(r#"pad_end("abc", 2) == "abc""#);
+        assert_true(r#"repeat("ab", 3) == "ababab""#);
+    }
+
+    #[test]
+    fn too_long() {
+        for code in [
+            r#"pad_start("abc", 10000000000000000)"#,
+            r#"pad_end("abc", 16777217)"#,
+            r#"repeat("ab", 10000000000000000)"#,
+            r#"repeat("ab", 8388609)"#,
+        ] {
+            assert_err(code, invalid_argument);
+        }
+        assert_true(r#"length(repeat("ab", 8388608)) == 16777216"#);
+    }
+
+    #[test]
+    fn empty_fill() {
+        let actual = eval_with_stdlib(r#"pad_start("a", 5, "")"#);
+        let Err(EvalError::InvalidArgument { span, .. }) = actual else {
+            panic!("expected invalid argument, got: {:?}", actual);
+        };
+        // points at the fill argument
+        assert_eq!((span.start(), span.end()), (18, 20));
+        assert_err(r#"pad_end("a", 1, "")"#, invalid_argument);
+    }
+
+    #[test]
+    fn chars_and_length() {
+        assert_true(r#"chars("añb") == ["a", "ñ", "b"]"#);
+        assert_true(r#"length("añb") == 3"#);
+    }
+
+    #[test]
+    fn type_errors() {
+        for code in [
+            r#"split(1, ",")"#,
+            r#"join(["a", 1], ",")"#,
+            r#"upper(null)"#,
+            r#"pad_start("a", -1)"#,
+            r#"pad_start("a", 1.5)"#,
+            r#"repeat("a", "b")"#,
+        ] {
+            assert_err(code, type_error);
+        }
+    }
+}
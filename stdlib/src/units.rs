@@ -0,0 +1,302 @@
+//! Functions to convert byte sizes and durations between human-readable
+//! strings and numbers.
+//!
+//! Byte sizes use SI (`k`, `M`, `G`, ...) or IEC (`Ki`, `Mi`, `Gi`, ...)
+//! units, as in Kubernetes quantities, optionally followed by `B`. Durations
+//! use the units of Go's `time.ParseDuration` (`ns`, `us`, `ms`, `s`, `m`,
+//! `h`), and are numbers of seconds. They're formatted as Go's
+//! `Duration.String` formats them, like `"1h30m0s"`.
+
+use std::str::FromStr;
+
+use rimu_meta::Spanned;
+use rimu_value::{EvalError, Function, Number, SerdeValueObject, Value};
+use rust_decimal::Decimal;
+
+use crate::{args, native_function};
+
+pub(crate) fn insert(lib: &mut SerdeValueObject) {
+    lib.insert("parse_bytes".into(), parse_bytes().into());
+    lib.insert("format_bytes".into(), format_bytes().into());
+    lib.insert("parse_duration".into(), parse_duration().into());
+    lib.insert("format_duration".into(), format_duration().into());
+}
+
+const KI: u64 = 1 << 10;
+
+const SI_UNITS: [(&str, u64); 6] = [
+    ("E", 1_000_000_000_000_000_000),
+    ("P", 1_000_000_000_000_000),
+    ("T", 1_000_000_000_000),
+    ("G", 1_000_000_000),
+    ("M", 1_000_000),
+    ("k", 1_000),
+];
+
+const IEC_UNITS: [(&str, u64); 6] = [
+    ("Ei", KI.pow(6)),
+    ("Pi", KI.pow(5)),
+    ("Ti", KI.pow(4)),
+    ("Gi", KI.pow(3)),
+    ("Mi", KI.pow(2)),
+    ("Ki", KI),
+];
+
+fn byte_unit(unit: &str) -> Option<u64> {
+    let unit = unit.strip_suffix('B').unwrap_or(unit);
+    match unit {
+        "" => Some(1),
+        // `K` is common for kilo, and unambiguous as no IEC unit lacks an `i`
+        "K" => Some(1_000),
+        _ => SI_UNITS
+            .iter()
+            .chain(IEC_UNITS.iter())
+            .find(|(name, _)| *name == unit)
+            .map(|(_, size)| *size),
+    }
+}
+
+/// Split a string into a leading number and the rest, as in `"1.5Gi"`.
+fn split_number(string: &str) -> Option<(Decimal, &str)> {
+    let end = string
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(string.len());
+    let (number, rest) = string.split_at(end);
+    if !number.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    Some((Decimal::from_str(number).ok()?, rest))
+}
+
+/// Parse a byte size, like `"512Mi"`, `"2GiB"` or `"1.5k"`, into a number of
+/// bytes, which must be whole.
+pub fn parse_bytes() -> Function {
+    native_function("parse_bytes", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        let invalid =
+            || args::invalid_argument(&args[0], format!("Invalid byte size: {:?}", string));
+        let (number, unit) = split_number(string.trim()).ok_or_else(invalid)?;
+        let size = byte_unit(unit.trim_start()).ok_or_else(invalid)?;
+        let bytes =
+            number
+                .checked_mul(Decimal::from(size))
+                .ok_or_else(|| EvalError::Arithmetic {
+                    span: span.clone(),
+                    message: "Result is too large to represent".into(),
+                })?;
+        if !bytes.fract().is_zero() {
+            return Err(args::invalid_argument(
+                &args[0],
+                format!("Not a whole number of bytes: {:?}", string),
+            ));
+        }
+        Ok(Spanned::new(
+            Value::Number(Number::from(bytes.normalize())),
+            span,
+        ))
+    })
+}
+
+/// Format a number of bytes with the largest unit which it's a whole number
+/// of, like `"512Mi"`. Uses IEC units, or SI units if the optional second
+/// argument is `"si"`.
+pub fn format_bytes() -> Function {
+    native_function("format_bytes", &["bytes"], |span, args| {
+        let bytes = match args[0].inner() {
+            Value::Number(number) if number.fract().is_zero() => number.to_u64(),
+            _ => None,
+        }
+        .ok_or_else(|| args::type_error(&args[0], "zero or positive integer"))?;
+        let units = match args.get(1) {
+            None => &IEC_UNITS,
+            Some(arg) => match args::string(arg)? {
+                "iec" => &IEC_UNITS,
+                "si" => &SI_UNITS,
+                other => {
+                    return Err(args::invalid_argument(
+                        arg,
+                        format!("Unknown units: {:?}, expected \"iec\" or \"si\"", other),
+                    ))
+                }
+            },
+        };
+        let string = units
+            .iter()
+            .find(|(_, size)| bytes != 0 && bytes % size == 0)
+            .map(|(name, size)| format!("{}{}", bytes / size, name))
+            .unwrap_or_else(|| bytes.to_string());
+        Ok(args::string_value(string, span))
+    })
+}
+
+fn duration_unit(unit: &str) -> Option<Decimal> {
+    let seconds = match unit {
+        "ns" => Decimal::new(1, 9),
+        "us" | "µs" | "μs" => Decimal::new(1, 6),
+        "ms" => Decimal::new(1, 3),
+        "s" => Decimal::ONE,
+        "m" => Decimal::from(60),
+        "h" => Decimal::from(3600),
+        _ => return None,
+    };
+    Some(seconds)
+}
+
+/// A duration, like `"1h30m"` or `"-1.5s"`, as a number of seconds.
+fn duration_seconds(string: &str) -> Option<Decimal> {
+    let (negative, mut rest) = match string.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, string.strip_prefix('+').unwrap_or(string)),
+    };
+    if rest == "0" {
+        return Some(Decimal::ZERO);
+    }
+    if rest.is_empty() {
+        return None;
+    }
+    let mut seconds = Decimal::ZERO;
+    while !rest.is_empty() {
+        let (number, after) = split_number(rest)?;
+        let end = after
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(after.len());
+        let (unit, after) = after.split_at(end);
+        seconds = seconds.checked_add(number.checked_mul(duration_unit(unit)?)?)?;
+        rest = after;
+    }
+    Some(if negative { -seconds } else { seconds })
+}
+
+/// Parse a duration, like `"30s"`, `"1h30m"` or `"250ms"`, into a number of
+/// seconds.
+pub fn parse_duration() -> Function {
+    native_function("parse_duration", &["string"], |span, args| {
+        let string = args::string(&args[0])?;
+        let seconds = duration_seconds(string.trim()).ok_or_else(|| {
+            args::invalid_argument(&args[0], format!("Invalid duration: {:?}", string))
+        })?;
+        Ok(Spanned::new(
+            Value::Number(Number::from(seconds.normalize())),
+            span,
+        ))
+    })
+}
+
+/// Format a number of seconds as a duration, like `"1h30m0s"`. Durations
+/// under a second use `ms`, `µs` or `ns`.
+pub fn format_duration() -> Function {
+    native_function("format_duration", &["seconds"], |span, args| {
+        let seconds = *args::number(&args[0])?;
+        Ok(args::string_value(duration_string(seconds), span))
+    })
+}
+
+fn duration_string(seconds: Decimal) -> String {
+    if seconds.is_zero() {
+        return "0s".into();
+    }
+    let sign = if seconds.is_sign_negative() { "-" } else { "" };
+    let seconds = seconds.abs();
+    if seconds < Decimal::ONE {
+        let (scale, unit) = if seconds >= Decimal::new(1, 3) {
+            (3, "ms")
+        } else if seconds >= Decimal::new(1, 6) {
+            (6, "µs")
+        } else {
+            (9, "ns")
+        };
+        let value = seconds * Decimal::from(10u64.pow(scale));
+        return format!("{}{}{}", sign, value.normalize(), unit);
+    }
+    let hours = (seconds / Decimal::from(3600)).trunc();
+    let minutes = ((seconds - hours * Decimal::from(3600)) / Decimal::from(60)).trunc();
+    let rest = seconds - hours * Decimal::from(3600) - minutes * Decimal::from(60);
+    // as in Go, leading zero units are left out but later ones aren't
+    let mut string = sign.to_string();
+    if !hours.is_zero() {
+        string.push_str(&format!("{}h", hours));
+    }
+    if !(hours.is_zero() && minutes.is_zero()) {
+        string.push_str(&format!("{}m", minutes));
+    }
+    string.push_str(&format!("{}s", rest.normalize()));
+    string
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{assert_err, assert_true, invalid_argument, type_error};
+
+    #[test]
+    fn parse_bytes() {
+        assert_true(r#"parse_bytes("512Mi") == 536870912"#);
+        assert_true(r#"parse_bytes("2GiB") == 2147483648"#);
+        assert_true(r#"parse_bytes("1.5k") == 1500 && parse_bytes("1.5K") == 1500"#);
+        assert_true(r#"parse_bytes("2 MB") == 2000000"#);
+        assert_true(r#"parse_bytes("100") == 100 && parse_bytes("100B") == 100"#);
+        assert_true(r#"parse_bytes("1Ei") == 1152921504606846976"#);
+    }
+
+    #[test]
+    fn format_bytes() {
+        assert_true(r#"format_bytes(536870912) == "512Mi""#);
+        assert_true(r#"format_bytes(parse_bytes("1Gi") / 2) == "512Mi""#);
+        assert_true(r#"format_bytes(1536) == "1536" && format_bytes(0) == "0""#);
+        assert_true(r#"format_bytes(2000000, "si") == "2M""#);
+        assert_true(r#"format_bytes(1500, "si") == "1500""#);
+        assert_true(r#"format_bytes(parse_bytes("16Ei") - 1) == "18446744073709551615""#);
+        assert_true(r#"format_bytes(parse_bytes("8Gi")) == "8Gi""#);
+    }
+
+    #[test]
+    fn parse_duration() {
+        assert_true(r#"parse_duration("30s") == 30"#);
+        assert_true(r#"parse_duration("1h30m") == 5400"#);
+        assert_true(r#"parse_duration("1.5h") == 5400"#);
+        assert_true(r#"parse_duration("250ms") == 0.25"#);
+        assert_true(r#"parse_duration("1us") == 0.000001 && parse_duration("1µs") == 0.000001"#);
+        assert_true(r#"parse_duration("10ns") == 0.00000001"#);
+        assert_true(r#"parse_duration("-1m30s") == -90"#);
+        assert_true(r#"parse_duration("0") == 0"#);
+    }
+
+    #[test]
+    fn format_duration() {
+        assert_true(r#"format_duration(5400) == "1h30m0s""#);
+        assert_true(r#"format_duration(90) == "1m30s""#);
+        assert_true(r#"format_duration(3630.5) == "1h0m30.5s""#);
+        assert_true(r#"format_duration(3600) == "1h0m0s""#);
+        assert_true(r#"format_duration(1.5) == "1.5s""#);
+        assert_true(r#"format_duration(0.25) == "250ms""#);
+        assert_true(r#"format_duration(0.0000015) == "1.5µs""#);
+        assert_true(r#"format_duration(0.00000001) == "10ns""#);
+        assert_true(r#"format_duration(-90) == "-1m30s""#);
+        assert_true(r#"format_duration(0) == "0s""#);
+        assert_true(r#"format_duration(parse_duration("1h30m") / 2) == "45m0s""#);
+    }
+
+    #[test]
+    fn errors() {
+        for code in [
+            r#"parse_bytes("12 parsecs")"#,
+            r#"parse_bytes("Mi")"#,
+            r#"parse_bytes("1.5")"#,
+            r#"parse_bytes("0.1Ki")"#,
+            r#"format_bytes(1024, "binary")"#,
+            r#"parse_duration("")"#,
+            r#"parse_duration("30")"#,
+            r#"parse_duration("1d")"#,
+            r#"parse_duration("1h30")"#,
+        ] {
+            assert_err(code, invalid_argument);
+        }
+        for code in [
+            r#"format_bytes(1.5)"#,
+            r#"format_bytes(-1)"#,
+            r#"format_bytes(parse_bytes("16Ei"))"#,
+            r#"format_duration("1s")"#,
+        ] {
+            assert_err(code, type_error);
+        }
+    }
+}
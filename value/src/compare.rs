@@ -0,0 +1,164 @@
+use std::cmp::Ordering;
+
+use crate::{Value, ValueObject};
+
+impl Value {
+    /// Whether two values are structurally equal, ignoring the spans of any
+    /// nested values and the order of object keys.
+    ///
+    /// Functions are only equal to the same function.
+    pub fn semantic_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Null, Value::Null) => true,
+            (Value::Boolean(a), Value::Boolean(b)) => a == b,
+            (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::HostPath(a), Value::HostPath(b)) => a == b,
+            (Value::TargetPath(a), Value::TargetPath(b)) => a == b,
+            (Value::List(a), Value::List(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|(a, b)| a.inner().semantic_eq(b.inner()))
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(key, a)| {
+                        b.get(key)
+                            .map(|b| a.inner().semantic_eq(b.inner()))
+                            .unwrap_or(false)
+                    })
+            }
+            (Value::Function(a), Value::Function(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// A total ordering over values, consistent with [`Value::semantic_eq`].
+    ///
+    /// Values of different kinds are ordered by kind: null, boolean, number,
+    /// string, host-path, target-path, list, object, then function. Lists are
+    /// ordered item by item, and objects entry by entry in order of their keys.
+    ///
+    /// Returns `None` when comparing different functions, which have no order.
+    pub fn semantic_cmp(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Null, Value::Null) => Some(Ordering::Equal),
+            (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
+            (Value::Number(a), Value::Number(b)) => Some(a.cmp(b)),
+            (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+            (Value::HostPath(a), Value::HostPath(b)) => Some(a.cmp(b)),
+            (Value::TargetPath(a), Value::TargetPath(b)) => Some(a.cmp(b)),
+            (Value::List(a), Value::List(b)) => {
+                for (a, b) in a.iter().zip(b.iter()) {
+                    match a.inner().semantic_cmp(b.inner())? {
+                        Ordering::Equal => continue,
+                        ordering => return Some(ordering),
+                    }
+                }
+                Some(a.len().cmp(&b.len()))
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                for ((a_key, a), (b_key, b)) in sorted_entries(a).zip(sorted_entries(b)) {
+                    match a_key.cmp(b_key) {
+                        Ordering::Equal => {}
+                        ordering => return Some(ordering),
+                    }
+                    match a.semantic_cmp(b)? {
+                        Ordering::Equal => continue,
+                        ordering => return Some(ordering),
+                    }
+                }
+                Some(a.len().cmp(&b.len()))
+            }
+            (Value::Function(a), Value::Function(b)) => (a == b).then_some(Ordering::Equal),
+            (a, b) => Some(a.kind_rank().cmp(&b.kind_rank())),
+        }
+    }
+
+    fn kind_rank(&self) -> u8 {
+        match self {
+            Value::Null => 0,
+            Value::Boolean(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::HostPath(_) => 4,
+            Value::TargetPath(_) => 5,
+            Value::List(_) => 6,
+            Value::Object(_) => 7,
+            Value::Function(_) => 8,
+        }
+    }
+}
+
+fn sorted_entries(object: &ValueObject) -> impl Iterator<Item = (&String, &Value)> {
+    let mut entries: Vec<_> = object
+        .iter()
+        .map(|(key, value)| (key, value.inner()))
+        .collect();
+    entries.sort_by_key(|(key, _)| *key);
+    entries.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use indexmap::indexmap;
+    use pretty_assertions::assert_eq;
+    use rimu_meta::{SourceId, Span, Spanned};
+    use rust_decimal_macros::dec;
+
+    use crate::{Number, Value};
+
+    fn spanned(value: Value, start: usize) -> Spanned<Value> {
+        Spanned::new(value, Span::new(SourceId::empty(), start, start + 1))
+    }
+
+    fn number(n: rust_decimal::Decimal) -> Value {
+        Value::Number(Number::from(n))
+    }
+
+    #[test]
+    fn eq_ignores_spans() {
+        let a = Value::List(vec![spanned(number(dec!(1)), 0)]);
+        let b = Value::List(vec![spanned(number(dec!(1)), 10)]);
+        assert!(a.semantic_eq(&b));
+        assert_eq!(a.semantic_cmp(&b), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn eq_ignores_key_order() {
+        let a = Value::Object(indexmap! {
+            "a".into() => spanned(number(dec!(1)), 0),
+            "b".into() => spanned(number(dec!(2)), 1),
+        });
+        let b = Value::Object(indexmap! {
+            "b".into() => spanned(number(dec!(2)), 2),
+            "a".into() => spanned(number(dec!(1)), 3),
+        });
+        assert!(a.semantic_eq(&b));
+        assert_eq!(a.semantic_cmp(&b), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn cmp_orders_by_kind() {
+        let values = vec![
+            Value::Null,
+            Value::Boolean(false),
+            Value::Boolean(true),
+            number(dec!(-1)),
+            number(dec!(2.5)),
+            Value::String("a".into()),
+            Value::String("b".into()),
+            Value::List(vec![]),
+            Value::List(vec![spanned(number(dec!(1)), 0)]),
+            Value::Object(indexmap! {}),
+        ];
+        for (i, a) in values.iter().enumerate() {
+            for (j, b) in values.iter().enumerate() {
+                assert_eq!(a.semantic_cmp(b), Some(i.cmp(&j)), "{:?} vs {:?}", a, b);
+            }
+        }
+    }
+}
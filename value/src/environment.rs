@@ -68,6 +68,12 @@ impl Environment {
         self.content.insert(k.into(), v.into());
     }
 
+    /// Remove a variable from this scope, returning its value if it was set.
+    /// Variables in a parent scope are left as-is.
+    pub fn remove(&mut self, key: &str) -> Option<SpannedValue> {
+        self.content.shift_remove(key)
+    }
+
     pub fn get(&self, key: &str) -> Option<SpannedValue> {
         match self.content.get(key) {
             Some(value) => Some(value.clone()),
@@ -94,6 +100,11 @@ impl Environment {
         let self_iter = self.content.clone().into_iter();
         Box::new(parent_iter.chain(self_iter))
     }
+
+    /// Iterate over the variables set in this scope, without any parent scope.
+    pub fn iter_local(&self) -> impl Iterator<Item = (&String, &SpannedValue)> {
+        self.content.iter()
+    }
 }
 
 /// Walk an object value down a key path. Returns the inner [`SpannedValue`]
@@ -178,4 +189,23 @@ mod tests {
             other => panic!("expected String, got {other:?}"),
         }
     }
+
+    #[test]
+    fn remove_only_affects_own_scope() {
+        let parent = Rc::new(RefCell::new(Environment::new()));
+        parent.borrow_mut().insert("a", SerdeValue::Boolean(true));
+        let mut env = Environment::new_with_parent(parent);
+        env.insert("a", SerdeValue::Boolean(false));
+        env.insert("b", SerdeValue::Null);
+
+        assert!(env.remove("a").is_some());
+        assert!(env.remove("a").is_none());
+
+        let local: Vec<&String> = env.iter_local().map(|(key, _)| key).collect();
+        assert_eq!(local, vec!["b"]);
+        assert_eq!(
+            env.get("a").map(|value| value.into_inner()),
+            Some(Value::Boolean(true))
+        );
+    }
 }
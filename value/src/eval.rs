@@ -37,6 +37,12 @@ pub enum EvalError {
         object_span: Span,
         object: Box<SerdeValueObject>,
     },
+    #[error("duplicate key: {key}")]
+    DuplicateKey { span: Span, key: String },
+    #[error("arithmetic error: {message}")]
+    Arithmetic { span: Span, message: String },
+    #[error("invalid argument: {message}")]
+    InvalidArgument { span: Span, message: String },
     #[error("range start >= end, start: {start}, end: {end}")]
     RangeStartGreaterThanOrEqualToEnd {
         span: Span,
@@ -123,6 +129,24 @@ impl From<EvalError> for ErrorReport {
                 ],
                 vec![],
             ),
+            EvalError::DuplicateKey { span, key } => (
+                span.clone(),
+                "Eval: Duplicate key",
+                vec![(span.clone(), format!("Key already in object: {}", key))],
+                vec![],
+            ),
+            EvalError::Arithmetic { span, message } => (
+                span.clone(),
+                "Eval: Arithmetic error",
+                vec![(span.clone(), message)],
+                vec![],
+            ),
+            EvalError::InvalidArgument { span, message } => (
+                span.clone(),
+                "Eval: Invalid argument",
+                vec![(span.clone(), message)],
+                vec![],
+            ),
             EvalError::RangeStartGreaterThanOrEqualToEnd { span, start, end } => (
                 span.clone(),
                 "Eval: Range start >= end",
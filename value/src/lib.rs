@@ -1,3 +1,4 @@
+mod compare;
 mod environment;
 mod eval;
 mod from;
@@ -149,6 +150,23 @@ impl SerdeValue {
     }
 }
 
+impl Value {
+    /// The name of the kind of value, as used in type errors.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Boolean(_) => "boolean",
+            Value::String(_) => "string",
+            Value::Number(_) => "number",
+            Value::Function(_) => "function",
+            Value::List(_) => "list",
+            Value::Object(_) => "object",
+            Value::HostPath(_) => "host-path",
+            Value::TargetPath(_) => "target-path",
+        }
+    }
+}
+
 impl Debug for Value {
     fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -1,21 +1,30 @@
+use std::{fmt, rc::Rc};
+
 use rimu_meta::{Span, Spanned};
 
 use crate::{EvalError, Value};
 
 type Args = [Spanned<Value>];
 
-#[derive(Debug, Clone)]
+type NativeFn = dyn Fn(Span, &Args) -> Result<Spanned<Value>, EvalError>;
+
+/// A function implemented in Rust. It may close over state, such as a cache
+/// shared by the functions of one stdlib.
+#[derive(Clone)]
 pub struct NativeFunction {
     name: &'static str,
-    function: fn(Span, &Args) -> Result<Spanned<Value>, EvalError>,
+    function: Rc<NativeFn>,
 }
 
 impl NativeFunction {
     pub fn new(
         name: &'static str,
-        function: fn(Span, &Args) -> Result<Spanned<Value>, EvalError>,
+        function: impl Fn(Span, &Args) -> Result<Spanned<Value>, EvalError> + 'static,
     ) -> Self {
-        Self { name, function }
+        Self {
+            name,
+            function: Rc::new(function),
+        }
     }
 
     pub fn call(&self, span: Span, args: &Args) -> Result<Spanned<Value>, EvalError> {
@@ -23,6 +32,14 @@ impl NativeFunction {
     }
 }
 
+impl fmt::Debug for NativeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativeFunction")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
 impl PartialEq for NativeFunction {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name
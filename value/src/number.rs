@@ -40,7 +40,27 @@ macro_rules! to_primitive {
     };
 }
 
+macro_rules! checked_op {
+    ($($method:ident),* $(,)?) => {
+        $(
+            /// Returns `None` if the result overflows, or divides by zero.
+            #[inline]
+            pub fn $method(self, rhs: Self) -> Option<Self> {
+                self.0.$method(rhs.0).map(Number)
+            }
+        )*
+    };
+}
+
 impl Number {
+    checked_op! {
+        checked_add,
+        checked_sub,
+        checked_mul,
+        checked_div,
+        checked_rem,
+    }
+
     from_primitive! {
         from_isize(isize),
         from_i8(i8),